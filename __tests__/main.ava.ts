@@ -89,10 +89,32 @@ workspace.test("Test get all projects", async (test, {
     reward: 2000,
     status: "Created",
     worker: null,
-    project_owner: company1.accountId
+    project_owner: company1.accountId,
+    token_id: null,
+    milestones: [],
+    paid_amount: 0,
+    unpaid_amount: 0,
+    unrefunded_amount: 0,
+    bidding: false,
+    submissions: [],
+    revisions: 0,
+    arbiter: null,
+    dispute: null,
+    apply_by: null,
+    deliver_by: null,
+    created_at: "",
+    updated_at: "",
+    assigned_at: null,
+    started_at: null,
+    submitted_at: null,
+    review_by: null,
+    completed_at: null
   }
   let projects: any = await contract.view("get_all_projects");
   test.is(projects.created.length, 1)
+  // Timestamps come from the sandbox blocks
+  expectedResponse.created_at = projects.created[0].created_at
+  expectedResponse.updated_at = projects.created[0].updated_at
   test.deepEqual(projects.created[0], expectedResponse)
   id = uuid().toString();
   args = {
@@ -106,6 +128,8 @@ workspace.test("Test get all projects", async (test, {
   test.is(projects.created.length, 2)
   expectedResponse.id = id
   expectedResponse.project_owner = company2.accountId
+  expectedResponse.created_at = projects.created[1].created_at
+  expectedResponse.updated_at = projects.created[1].updated_at
   test.deepEqual(projects.created[1], expectedResponse)
 })
 
@@ -137,10 +161,10 @@ workspace.test("Test that a user cannot edit another users project", async (test
     github_issue_link: "https://github.com/dummy-repo/issues/1"
   }
   let result: any = await company2.call(contract, 'update_project', args)
-  test.is(result.Err, 'You can only edit projects you own.')
+  test.is(result.Err.code, 'NOT_OWNER')
   args.id = return_id2.Ok
   result = await company1.call(contract, 'update_project', args)
-  test.is(result.Err, 'You can only edit projects you own.')
+  test.is(result.Err.code, 'NOT_OWNER')
   result = await company2.call(contract, 'update_project', args)
   test.is(result.Err, undefined)
 })
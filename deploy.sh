@@ -8,4 +8,4 @@ near create-account near_project_management."$ACCOUNT_ID" --masterAccount "$ACCO
 
 near deploy near_project_management."$ACCOUNT_ID" ./target/wasm32-unknown-unknown/release/near_project_management.wasm
 
-near call near_project_management."$ACCOUNT_ID" new --accountId near_project_management."$ACCOUNT_ID"
+near call near_project_management."$ACCOUNT_ID" new "{\"owner_id\": \"$ACCOUNT_ID\"}" --accountId near_project_management."$ACCOUNT_ID"
@@ -1,22 +1,54 @@
 pub mod structs {
     pub mod all_projects_return;
+    pub mod application;
+    pub mod bid;
+    pub mod dispute;
+    pub mod escrow_summary;
+    pub mod ft_project_message;
+    pub mod history_entry;
+    pub mod milestone;
     pub mod project;
+    pub mod project_error;
+    pub mod project_event;
+    pub mod project_filter;
     pub mod project_management;
+    pub mod project_management_v1;
+    pub mod project_page;
+    pub mod project_sort;
+    pub mod project_v1;
+    pub mod role;
+    pub mod submission;
+    pub mod transition;
     pub mod user_project_returns;
+    pub mod versioned_project;
 }
 
 #[cfg(test)]
+// The original tests compare booleans with assert_eq! and cast literals
+#[allow(clippy::bool_assert_comparison, clippy::unnecessary_cast)]
 mod tests {
+    use crate::structs::application::ApplicationStatus;
+    use crate::structs::bid::BidStatus;
+    use crate::structs::milestone::{MilestoneInput, MilestoneStatus};
+    use crate::structs::project::{Project, Status};
+    use crate::structs::project_error::ProjectError;
+    use crate::structs::project_filter::ProjectFilter;
+    use crate::structs::project_sort::{ProjectSort, SortField};
+    use crate::structs::project_v1::{ProjectV1, StatusV1};
     use crate::structs::project_management::ProjectManagement;
-    use near_sdk::{testing_env, VMContext};
+    use crate::structs::project_management_v1::ProjectManagementV1;
+    use near_sdk::collections::{LookupMap, UnorderedSet};
+    use near_sdk::json_types::{U128, U64};
+    use near_sdk::test_utils::{get_logs, testing_env_with_promise_results};
+    use near_sdk::{env, serde_json, testing_env, PromiseResult, VMContext};
     use near_sdk::{AccountId, MockedBlockchain};
 
     fn get_context(input: Vec<u8>, is_view: bool, signer: String) -> VMContext {
         VMContext {
             current_account_id: "alice.testnet".to_string(),
-            signer_account_id: signer,
+            signer_account_id: signer.clone(),
             signer_account_pk: vec![0, 1, 2],
-            predecessor_account_id: "jane.testnet".to_string(),
+            predecessor_account_id: signer,
             input,
             block_index: 0,
             block_timestamp: 0,
@@ -32,6 +64,51 @@ mod tests {
         }
     }
 
+    // Switches to a callback context while keeping the storage usage of the earlier calls
+    fn set_promise_result(mut context: VMContext, result: PromiseResult) {
+        context.storage_usage = env::storage_usage();
+        testing_env_with_promise_results(context, result);
+    }
+
+    // Switches the signer while keeping the storage usage of the earlier calls
+    fn set_signer(signer: &str) {
+        set_signer_with_deposit(signer, 2000);
+    }
+
+    fn set_signer_with_deposit(signer: &str, attached_deposit: u128) {
+        let mut context = get_context(vec![], false, signer.to_string());
+        context.storage_usage = env::storage_usage();
+        context.attached_deposit = attached_deposit;
+        testing_env!(context);
+    }
+
+    // Puts the worker forward for the job and switches back to the company
+    fn volunteer(contract: &mut ProjectManagement, id: String, worker: &str) {
+        set_signer(worker);
+        assert!(contract.set_user_for_project(id, worker.to_string()).is_ok());
+        set_signer("alice.testnet");
+    }
+
+    fn set_signer_at(signer: &str, block_timestamp: u64) {
+        let mut context = get_context(vec![], false, signer.to_string());
+        context.storage_usage = env::storage_usage();
+        context.block_timestamp = block_timestamp;
+        testing_env!(context);
+    }
+
+    // Checks that the call fails the whole transaction, as payable methods do on an error, and
+    // hands back the panic message
+    fn assert_fails<T>(call: impl FnOnce() -> T) -> String {
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(call))
+            .err()
+            .expect("the call should fail");
+        panic
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| panic.downcast_ref::<&str>().map(|message| message.to_string()))
+            .unwrap_or_default()
+    }
+
     #[test]
     // This test should insert a new project with no errors
     fn test_get_all_projects() {
@@ -39,15 +116,19 @@ mod tests {
         let context = get_context(vec![], false, "alice.testnet".to_string());
         testing_env!(context.clone());
         // instantiate a contract
-        let mut contract = ProjectManagement::new();
+        // The company cannot take its own jobs, so another account works on them
+        let worker_account = AccountId::from("bob.testnet");
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
         for i in 1..5 {
             let result = contract.add_project(
                 i.to_string(),
                 "https://github.com/test-project/issues/1".to_string(),
-                "This is a test".to_string()
+                "This is a test".to_string(),
+                None,
+                None,
             );
             assert_eq!(result.is_ok(), true);
-            let projects = contract.get_all_projects();
+            let projects = contract.get_all_projects(None, None);
             assert_eq!(projects.created.len(), i as usize);
             assert_eq!(projects.not_started.len(), 0 as usize);
             assert_eq!(projects.in_progress.len(), 0 as usize);
@@ -55,18 +136,28 @@ mod tests {
             assert_eq!(projects.created[0].reward, 2000 as u128)
         }
         for i in 1..3 {
-            let result =
-                contract.set_user_for_project(i.to_string(), context.clone().current_account_id);
+            set_signer(&worker_account);
+            let result = contract.set_user_for_project(i.to_string(), worker_account.clone());
             assert_eq!(result.is_ok(), true);
-            let projects = contract.get_all_projects();
+            set_signer("alice.testnet");
+            let projects = contract.get_all_projects(None, None);
             assert_eq!(projects.pending_work_approval.len(), i as usize);
             assert_eq!(projects.in_progress.len(), 0 as usize);
             assert_eq!(projects.complete.len(), 0 as usize);
         }
         for i in 1..3 {
-            let result = contract.approve_submission(i.to_string().clone(), true);
+            assert!(contract.approve_user_for_project(i.to_string(), true).is_ok());
+            set_signer(&worker_account);
+            let result = contract.submit_work(
+                i.to_string(),
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            );
+            assert_eq!(result.is_ok(), true);
+            set_signer("alice.testnet");
+            let result = contract.approve_submission(i.to_string().clone(), true, None);
             assert_eq!(result.is_ok(), true);
-            let projects = contract.get_all_projects();
+            let projects = contract.get_all_projects(None, None);
             assert_eq!(projects.pending_work_approval.len(), 2 - i as usize);
             assert_eq!(projects.in_progress.len(), 0 as usize);
             assert_eq!(projects.complete.len(), i as usize);
@@ -79,30 +170,37 @@ mod tests {
         let context = get_context(vec![], false, "alice.testnet".to_string());
         testing_env!(context.clone());
         // instantiate a contract
-        let mut contract = ProjectManagement::new();
+        // The company cannot take its own jobs, so another account works on them
+        let worker_account = AccountId::from("bob.testnet");
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
         for i in 1..5 {
             let result = contract.add_project(
                 i.to_string(),
                 "https://github.com/test-project/issues/1".to_string(),
-                "This is a test".to_string()
+                "This is a test".to_string(),
+                None,
+                None,
             );
             assert_eq!(result.is_ok(), true);
-            let projects = contract.get_user_projects(context.clone().current_account_id);
+            let projects =
+                contract.get_user_projects(context.clone().current_account_id, None, None);
             assert_eq!(projects.in_progress.len(), 0 as usize);
             assert_eq!(projects.complete.len(), 0 as usize);
         }
         for i in 1..3 {
-            let result =
-                contract.set_user_for_project(i.to_string(), context.clone().current_account_id);
+            set_signer(&worker_account);
+            let result = contract.set_user_for_project(i.to_string(), worker_account.clone());
             assert_eq!(result.is_ok(), true);
-            let projects = contract.get_user_projects(context.clone().current_account_id);
+            set_signer("alice.testnet");
+            let projects = contract.get_user_projects(worker_account.clone(), None, None);
             assert_eq!(projects.pending_work_approval.len(), i as usize);
             assert_eq!(projects.complete.len(), 0 as usize);
         }
         for i in 1..3 {
+            assert!(contract.approve_user_for_project(i.to_string(), true).is_ok());
             let result = contract.set_project_complete(i.to_string());
             assert_eq!(result.is_ok(), true);
-            let projects = contract.get_user_projects(context.clone().current_account_id);
+            let projects = contract.get_user_projects(worker_account.clone(), None, None);
             assert_eq!(projects.pending_work_approval.len(), 2 - i as usize);
             assert_eq!(projects.complete.len(), i as usize);
         }
@@ -115,11 +213,13 @@ mod tests {
         let context = get_context(vec![], false, "alice.testnet".to_string());
         testing_env!(context.clone());
         // instantiate a contract
-        let mut contract = ProjectManagement::new();
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
         let result = contract.add_project(
             "1".parse().unwrap(),
             "https://github.com/test-project/issues/1".to_string(),
-            "This is a test".to_string()
+            "This is a test".to_string(),
+            None,
+            None,
         );
         assert_eq!(result.is_ok(), true);
     }
@@ -132,25 +232,1989 @@ mod tests {
         testing_env!(context.clone());
         // instantiate a contract
         let worker_account = AccountId::from("subaccount.example.near");
-        let mut contract = ProjectManagement::new();
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
         let id = contract.add_project(
             "1".parse().unwrap(),
             "https://github.com/test-project/issues/1".to_string(),
-            "This is a test".to_string()
+            "This is a test".to_string(),
+            None,
+            None,
         );
         assert_eq!(id.is_ok(), true);
+        set_signer(&worker_account);
         let result = contract.set_user_for_project(id.clone().unwrap(), worker_account.clone());
         assert_eq!(result.is_ok(), true);
+        set_signer("alice.testnet");
         // This second part test that it can add to a existing set
         let id = contract.add_project(
             "2".parse().unwrap(),
             "https://github.com/test-project/issues/2".to_string(),
-            "This is a test 2".to_string()
+            "This is a test 2".to_string(),
+            None,
+            None,
         );
         assert_eq!(id.is_ok(), true);
+        set_signer(&worker_account);
         let result = contract.set_user_for_project(id.clone().unwrap(), worker_account.clone());
         assert_eq!(result.is_ok(), true);
-        let result = contract.approve_submission(id.unwrap().clone(), true);
+        set_signer("alice.testnet");
+        let result = contract.approve_submission(id.clone().unwrap(), true, None);
+        assert_eq!(result.is_err(), true);
+        assert!(contract.approve_user_for_project(id.clone().unwrap(), true).is_ok());
+        set_signer(&worker_account);
+        let result = contract.submit_work(
+            id.clone().unwrap(),
+            "https://github.com/test-project/pull/2".to_string(),
+            "Done".to_string()
+        );
+        assert_eq!(result.is_ok(), true);
+        set_signer("alice.testnet");
+        let result = contract.approve_submission(id.unwrap().clone(), true, None);
         assert_eq!(result.is_ok(), true);
     }
+
+    #[test]
+    // This test should remove a created project and hand the reward back through a promise
+    fn test_remove_project_refunds_reward() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract.add_project(
+            "1".to_string(),
+            "https://github.com/test-project/issues/1".to_string(),
+            "This is a test".to_string(),
+            None,
+            None,
+        );
+        assert!(id.is_ok());
+        let project = contract.find_project(id.as_ref().unwrap()).unwrap();
+        let result = contract.remove_project(id.unwrap());
+        assert!(result.is_ok());
+        assert!(contract.get_all_projects(None, None).created.is_empty());
+        set_promise_result(context, PromiseResult::Successful(vec![]));
+        assert!(contract.resolve_project_refund(project));
+        assert!(contract.get_all_projects(None, None).created.is_empty());
+        // Once the refund went through the id can be used again, with a timeline of its own
+        set_signer("carol.testnet");
+        assert!(contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/2".to_string(),
+                "Another test".to_string(),
+                None,
+                None,
+            )
+            .is_ok());
+        let history = contract.get_project_history("1".to_string(), None, None);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, "project_created".to_string());
+        assert_eq!(history[0].actor, "carol.testnet".to_string());
+        assert_eq!(history[0].previous_status, None);
+    }
+
+    #[test]
+    // This test should put the project back when the refund transfer fails
+    fn test_remove_project_restored_on_failed_refund() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract.add_project(
+            "1".to_string(),
+            "https://github.com/test-project/issues/1".to_string(),
+            "This is a test".to_string(),
+            None,
+            None,
+        );
+        let project = contract.find_project(id.as_ref().unwrap()).unwrap();
+        assert!(contract.remove_project(id.unwrap()).is_ok());
+        // The id stays taken until the refund comes back
+        set_signer_with_deposit("carol.testnet", 9000);
+        let message = assert_fails(|| {
+            contract.add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/2".to_string(),
+                "Another test".to_string(),
+                None,
+                None,
+            )
+        });
+        assert!(message.contains(&ProjectError::ProjectAlreadyExists.to_string()));
+        set_promise_result(context, PromiseResult::Failed);
+        assert!(!contract.resolve_project_refund(project));
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.created.len(), 1);
+        assert_eq!(projects.created[0].reward, 2000);
+        assert_eq!(projects.created[0].project_owner, "alice.testnet".to_string());
+    }
+
+    #[test]
+    // Only the company that created the project can remove it
+    fn test_remove_project_requires_owner() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract.add_project(
+            "1".to_string(),
+            "https://github.com/test-project/issues/1".to_string(),
+            "This is a test".to_string(),
+            None,
+            None,
+        );
+        set_signer("bob.testnet");
+        assert!(contract.remove_project(id.unwrap()).is_err());
+        assert_eq!(contract.get_all_projects(None, None).created.len(), 1);
+    }
+
+    #[test]
+    // Topping up a project should add the attached deposit to the reward
+    fn test_increase_reward() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract.add_project(
+            "1".to_string(),
+            "https://github.com/test-project/issues/1".to_string(),
+            "This is a test".to_string(),
+            None,
+            None,
+        );
+        let result = contract.increase_reward(id.clone().unwrap());
+        assert_eq!(result, Ok(4000));
+        assert_eq!(contract.find_project(&id.unwrap()).unwrap().reward, 4000);
+    }
+
+    #[test]
+    // A top up that fails should fail the call, so the deposit goes back and is not escrowed
+    fn test_increase_reward_failure_returns_deposit() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        set_signer_with_deposit("bob.testnet", 5000);
+        let message = assert_fails(|| contract.increase_reward(id.clone()));
+        // The panic carries the serialized error, code included
+        assert!(message.contains(ProjectError::NotOwner.code()));
+        assert!(message.contains(&ProjectError::NotOwner.to_string()));
+        assert_eq!(contract.get_project(&id).unwrap().reward, 2000);
+        assert_eq!(contract.total_escrowed, 2000);
+        assert!(contract.escrow_ledger.get(&"bob.testnet".to_string()).is_none());
+    }
+
+    #[test]
+    // Lowering the reward should only be allowed up to the escrowed amount
+    fn test_decrease_reward() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(contract.decrease_reward(id.clone(), "3000".to_string()).is_err());
+        assert!(contract.decrease_reward(id.clone(), "abc".to_string()).is_err());
+        assert!(contract.decrease_reward(id.clone(), "500".to_string()).is_ok());
+        assert_eq!(contract.find_project(&id).unwrap().reward, 1500);
+        set_promise_result(context, PromiseResult::Failed);
+        assert!(!contract.resolve_reward_refund(id.clone(), 500));
+        assert_eq!(contract.find_project(&id).unwrap().reward, 2000);
+        let actions: Vec<String> = contract
+            .get_project_history(id, None, None)
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect();
+        assert_eq!(actions, vec!["project_created", "reward_changed", "refund_failed"]);
+    }
+
+    #[test]
+    // The escrow ledger should follow deposits, refunds and payouts for each owner
+    fn test_get_escrow_summary() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        for i in 1..4 {
+            let result = contract.add_project(
+                i.to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            );
+            assert!(result.is_ok());
+        }
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(6000));
+        assert!(contract.decrease_reward("1".to_string(), "500".to_string()).is_ok());
+        assert!(contract.remove_project("2".to_string()).is_ok());
+        volunteer(&mut contract, "3".to_string(), "bob.testnet");
+        assert!(contract.approve_user_for_project("3".to_string(), true).is_ok());
+        assert!(contract.set_project_complete("3".to_string()).is_ok());
+        let summary = contract.get_escrow_summary();
+        assert_eq!(summary.total_escrowed, U128(1500));
+        assert_eq!(summary.total_refunded, U128(2500));
+        assert_eq!(summary.total_paid_out, U128(2000));
+        assert!(summary.solvent);
+        assert_eq!(summary.owners.len(), 1);
+        assert_eq!(summary.owners[0].owner_id, "alice.testnet".to_string());
+        assert_eq!(summary.owners[0].escrowed, U128(1500));
+        // Amounts are strings in JSON so they keep their precision
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["total_escrowed"], "1500");
+        assert_eq!(json["owners"][0]["refunded"], "2500");
+    }
+
+    #[test]
+    // A token transfer with a valid msg should create a project paid in that token
+    fn test_ft_on_transfer_creates_project() {
+        let mut context = get_context(vec![], false, "alice.testnet".to_string());
+        // The token contract is the one calling ft_on_transfer
+        context.predecessor_account_id = "token.testnet".to_string();
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let msg = r#"{"id":"1","github_issue_link":"https://github.com/test-project/issues/1","description":"This is a test"}"#;
+        contract.ft_on_transfer("alice.testnet".to_string(), U128(5000), msg.to_string());
+        set_signer("alice.testnet");
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.created.len(), 1);
+        assert_eq!(projects.created[0].reward, 5000);
+        assert_eq!(projects.created[0].project_owner, "alice.testnet".to_string());
+        assert_eq!(projects.created[0].token_id, Some("token.testnet".to_string()));
+        // Token rewards are not part of the NEAR escrow
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(0));
+        volunteer(&mut contract, "1".to_string(), "bob.testnet");
+        assert!(contract.approve_user_for_project("1".to_string(), true).is_ok());
+        assert!(contract.set_project_complete("1".to_string()).is_ok());
+        assert_eq!(contract.get_escrow_summary().total_paid_out, U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = r#"{\"code\":\"INVALID_MESSAGE\",\"message\":\"The msg must contain an id, github_issue_link and description.\"}"#)]
+    fn test_ft_on_transfer_rejects_invalid_msg() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        contract.ft_on_transfer("alice.testnet".to_string(), U128(5000), "1".to_string());
+    }
+
+    fn milestone(title: &str, amount: &str) -> MilestoneInput {
+        MilestoneInput {
+            title: title.to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    #[test]
+    // Milestones should be paid one at a time and complete the project after the last one
+    fn test_milestone_payouts() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        let result = contract.set_milestones(id.clone(), vec![milestone("design", "500")]);
+        assert!(result.is_err());
+        let result = contract.set_milestones(
+            id.clone(),
+            vec![milestone("design", "0"), milestone("build", "2000")],
+        );
+        assert_eq!(result, Err(ProjectError::ZeroAmount));
+        let max = u128::MAX.to_string();
+        let result = contract.set_milestones(
+            id.clone(),
+            vec![milestone("design", &max), milestone("build", "2001")],
+        );
+        assert_eq!(result, Err(ProjectError::InvalidAmount { amount: "2001".to_string() }));
+        let result = contract.set_milestones(
+            id.clone(),
+            vec![milestone("design", "1200"), milestone("build", "800")],
+        );
+        assert!(result.is_ok());
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        assert!(contract.set_project_complete(id.clone()).is_err());
+        // The worker took the job on these terms
+        let result = contract.set_milestones(id.clone(), vec![]);
+        assert_eq!(result, Err(ProjectError::MilestonesLocked));
+
+        set_signer("bob.testnet");
+        assert!(contract.submit_milestone(id.clone(), 1).is_err());
+        assert!(contract.submit_milestone(id.clone(), 0).is_ok());
+        set_signer("alice.testnet");
+        assert!(contract.approve_milestone(id.clone(), 1).is_err());
+        assert!(contract.approve_milestone(id.clone(), 0).is_ok());
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.in_progress.len(), 1);
+        assert_eq!(projects.in_progress[0].milestones[0].status, MilestoneStatus::Approved);
+        assert_eq!(contract.get_escrow_summary().total_paid_out, U128(1200));
+
+        set_signer("bob.testnet");
+        assert!(contract.submit_milestone(id.clone(), 1).is_ok());
+        set_signer("alice.testnet");
+        assert!(contract.approve_milestone(id.clone(), 1).is_ok());
+        let projects = contract.get_user_projects("bob.testnet".to_string(), None, None);
+        assert_eq!(projects.complete.len(), 1);
+        assert_eq!(projects.complete[0].status, Status::Complete);
+        assert_eq!(projects.complete[0].milestones[1].status, MilestoneStatus::Approved);
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(0));
+    }
+
+    #[test]
+    // A bounced payout should be recorded and be recoverable with retry_payout
+    fn test_retry_failed_payout() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        assert!(contract.set_project_complete(id.clone()).is_ok());
+        assert!(contract.retry_payout(id.clone()).is_err());
+        set_promise_result(context.clone(), PromiseResult::Failed);
+        assert!(!contract.resolve_payout(id.clone(), "bob.testnet".to_string(), 2000));
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.payout_failed.len(), 1);
+        assert_eq!(projects.payout_failed[0].unpaid_amount, 2000);
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(2000));
+        // The reward has already been released, so a top up would never be paid out
+        set_signer_with_deposit("alice.testnet", 5000);
+        let message = assert_fails(|| contract.increase_reward(id.clone()));
+        assert!(message.contains(&ProjectError::RewardLocked.to_string()));
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(2000));
+
+        set_signer("carol.testnet");
+        assert!(contract.retry_payout(id.clone()).is_err());
+        assert!(contract.claim_payout(id.clone(), "carol.testnet".to_string()).is_err());
+        set_signer("alice.testnet");
+        assert!(contract.retry_payout(id.clone()).is_ok());
+        set_promise_result(context, PromiseResult::Successful(vec![]));
+        assert!(contract.resolve_payout(id.clone(), "bob.testnet".to_string(), 2000));
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.complete.len(), 1);
+        assert_eq!(projects.complete[0].paid_amount, 2000);
+        assert_eq!(projects.complete[0].unpaid_amount, 0);
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(0));
+    }
+
+    #[test]
+    // The worker can collect a bounced payout on a different account
+    fn test_claim_payout() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        assert!(contract.set_project_complete(id.clone()).is_ok());
+        set_promise_result(context, PromiseResult::Failed);
+        assert!(!contract.resolve_payout(id.clone(), "bob.testnet".to_string(), 2000));
+        set_signer("bob.testnet");
+        assert!(contract.claim_payout(id.clone(), "bob2.testnet".to_string()).is_ok());
+        assert_eq!(contract.get_all_projects(None, None).complete.len(), 1);
+    }
+
+    #[test]
+    // Nobody but the worker can send a bounced payout to another account
+    fn test_only_worker_redirects_payout() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("admin.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        assert!(contract.set_project_complete(id.clone()).is_ok());
+        set_promise_result(context, PromiseResult::Failed);
+        assert!(!contract.resolve_payout(id.clone(), "bob.testnet".to_string(), 2000));
+        set_signer("alice.testnet");
+        assert_eq!(
+            contract.claim_payout(id.clone(), "alice.testnet".to_string()).err(),
+            Some(ProjectError::NotWorker)
+        );
+        set_signer("admin.testnet");
+        assert_eq!(
+            contract.claim_payout(id.clone(), "admin.testnet".to_string()).err(),
+            Some(ProjectError::NotWorker)
+        );
+        assert_eq!(contract.get_all_projects(None, None).payout_failed.len(), 1);
+    }
+
+    #[test]
+    // Volunteering for a job should follow the same rules as applying to it
+    fn test_set_user_for_project_rules() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = add_bidding_project(&mut contract);
+        assert_eq!(
+            contract.set_user_for_project(id.clone(), "alice.testnet".to_string()),
+            Err(ProjectError::BiddingOnly)
+        );
+        assert!(contract.set_bidding(id.clone(), false).is_ok());
+        assert_eq!(
+            contract.set_user_for_project(id.clone(), "alice.testnet".to_string()),
+            Err(ProjectError::OwnProject)
+        );
+        assert!(contract
+            .update_project(id.clone(), None, None, Some(U64(100)), None)
+            .is_ok());
+        set_signer_at("bob.testnet", 200);
+        assert_eq!(
+            contract.set_user_for_project(id.clone(), "bob.testnet".to_string()),
+            Err(ProjectError::ApplicationDeadlinePassed)
+        );
+        assert_eq!(contract.get_project(&id).unwrap().status, Status::Created);
+    }
+
+    #[test]
+    // Accepting one application should assign the worker and decline the rest
+    fn test_accept_application() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(contract
+            .apply_to_project(id.clone(), "I own this job".to_string(), None)
+            .is_err());
+        set_signer("bob.testnet");
+        assert!(contract
+            .apply_to_project(id.clone(), "I can do it".to_string(), Some("2022-06-01".to_string()))
+            .is_ok());
+        assert!(contract
+            .apply_to_project(id.clone(), "Again".to_string(), None)
+            .is_err());
+        set_signer("carol.testnet");
+        assert!(contract
+            .apply_to_project(id.clone(), "Me too".to_string(), None)
+            .is_ok());
+        assert!(contract
+            .accept_application(id.clone(), "carol.testnet".to_string())
+            .is_err());
+        set_signer("alice.testnet");
+        assert!(contract
+            .accept_application(id.clone(), "dave.testnet".to_string())
+            .is_err());
+        assert!(contract
+            .accept_application(id.clone(), "bob.testnet".to_string())
+            .is_ok());
+        let applications = contract.get_project_applications(id.clone());
+        assert_eq!(applications.len(), 2);
+        assert_eq!(applications[0].status, ApplicationStatus::Accepted);
+        assert_eq!(applications[1].status, ApplicationStatus::Declined);
+        let projects = contract.get_user_projects("bob.testnet".to_string(), None, None);
+        assert_eq!(projects.not_started.len(), 1);
+        set_signer("dave.testnet");
+        assert!(contract
+            .apply_to_project(id, "Too late".to_string(), None)
+            .is_err());
+    }
+
+    fn add_bidding_project(contract: &mut ProjectManagement) -> String {
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(contract.set_bidding(id.clone(), true).is_ok());
+        set_signer("bob.testnet");
+        assert!(contract
+            .apply_to_project(id.clone(), "I can do it".to_string(), None)
+            .is_err());
+        assert!(contract
+            .submit_bid(id.clone(), "1500".to_string(), "two weeks".to_string())
+            .is_ok());
+        set_signer("carol.testnet");
+        assert!(contract
+            .submit_bid(id.clone(), "3000".to_string(), "one week".to_string())
+            .is_ok());
+        set_signer("alice.testnet");
+        id
+    }
+
+    #[test]
+    // Accepting a lower bid should assign the worker and refund the surplus
+    fn test_accept_lower_bid() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = add_bidding_project(&mut contract);
+        set_signer_with_deposit("alice.testnet", 0);
+        let result = contract.accept_bid(id.clone(), "bob.testnet".to_string());
+        assert!(result.unwrap().is_some());
+        let bids = contract.get_project_bids(id.clone());
+        assert_eq!(bids[0].status, BidStatus::Accepted);
+        assert_eq!(bids[1].status, BidStatus::Declined);
+        let project = contract.find_project(&id).unwrap();
+        assert_eq!(project.reward, 1500);
+        assert_eq!(project.worker, Some("bob.testnet".to_string()));
+        assert_eq!(project.status, Status::NotStarted);
+        assert_eq!(contract.get_escrow_summary().total_refunded, U128(500));
+    }
+
+    #[test]
+    // A bounced surplus refund should stay owed to the company instead of raising the reward
+    fn test_accept_lower_bid_with_bounced_surplus() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = add_bidding_project(&mut contract);
+        set_signer_with_deposit("alice.testnet", 0);
+        assert!(contract
+            .accept_bid(id.clone(), "bob.testnet".to_string())
+            .unwrap()
+            .is_some());
+        set_promise_result(context, PromiseResult::Failed);
+        assert!(!contract.resolve_owner_refund(id.clone(), 500));
+        let project = contract.get_project(&id).unwrap();
+        assert_eq!(project.reward, 1500);
+        assert_eq!(project.unrefunded_amount, 500);
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(2000));
+        set_signer("alice.testnet");
+        assert!(contract.claim_refund(id.clone()).is_ok());
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(1500));
+    }
+
+    #[test]
+    // Removing a project should also hand back a refund that bounced earlier
+    fn test_remove_project_refunds_bounced_surplus() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = add_bidding_project(&mut contract);
+        set_signer_with_deposit("alice.testnet", 0);
+        assert!(contract
+            .accept_bid(id.clone(), "bob.testnet".to_string())
+            .unwrap()
+            .is_some());
+        set_promise_result(context.clone(), PromiseResult::Failed);
+        assert!(!contract.resolve_owner_refund(id.clone(), 500));
+        set_signer("bob.testnet");
+        assert!(contract
+            .remove_user_from_project(id.clone(), "bob.testnet".to_string())
+            .is_ok());
+        set_signer("alice.testnet");
+        let project = contract.get_project(&id).unwrap();
+        assert!(contract.remove_project(id.clone()).is_ok());
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(0));
+        assert_eq!(contract.get_escrow_summary().total_refunded, U128(2000));
+
+        // A bounce puts both back
+        set_promise_result(context, PromiseResult::Failed);
+        assert!(!contract.resolve_project_refund(project));
+        let project = contract.get_project(&id).unwrap();
+        assert_eq!(project.reward, 1500);
+        assert_eq!(project.unrefunded_amount, 500);
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(2000));
+    }
+
+    #[test]
+    // A bid that cannot be accepted should fail the call so the attached top up goes back
+    fn test_accept_bid_failure_returns_deposit() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = add_bidding_project(&mut contract);
+        set_signer_with_deposit("alice.testnet", 1000);
+        let message = assert_fails(|| contract.accept_bid(id.clone(), "dave.testnet".to_string()));
+        assert!(message.contains("dave.testnet has no pending bid"));
+        set_signer_with_deposit("bob.testnet", 1000);
+        let message = assert_fails(|| contract.accept_bid(id.clone(), "bob.testnet".to_string()));
+        assert!(message.contains(&ProjectError::NotOwner.to_string()));
+        assert_eq!(contract.get_project(&id).unwrap().reward, 2000);
+        assert_eq!(contract.total_escrowed, 2000);
+        assert!(contract
+            .get_project_bids(id)
+            .iter()
+            .all(|bid| bid.status == BidStatus::Pending));
+    }
+
+    #[test]
+    // Accepting a higher bid should wait for the company to top up the reward
+    fn test_accept_higher_bid_after_top_up() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = add_bidding_project(&mut contract);
+        set_signer_with_deposit("alice.testnet", 0);
+        let result = contract.accept_bid(id.clone(), "carol.testnet".to_string());
+        assert!(result.unwrap().is_none());
+        assert_eq!(contract.get_project_bids(id.clone())[1].status, BidStatus::AwaitingTopUp);
+        assert_eq!(contract.find_project(&id).unwrap().status, Status::Created);
+        set_signer_with_deposit("alice.testnet", 1000);
+        assert_eq!(contract.increase_reward(id.clone()), Ok(3000));
+        let bids = contract.get_project_bids(id.clone());
+        assert_eq!(bids[0].status, BidStatus::Declined);
+        assert_eq!(bids[1].status, BidStatus::Accepted);
+        let project = contract.find_project(&id).unwrap();
+        assert_eq!(project.worker, Some("carol.testnet".to_string()));
+        assert_eq!(project.status, Status::NotStarted);
+    }
+
+    #[test]
+    // Only the assigned worker can submit work and it should be kept on the project
+    fn test_submit_work() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        let result = contract.submit_work(
+            id.clone(),
+            "https://github.com/test-project/pull/1".to_string(),
+            "Done".to_string()
+        );
+        assert!(result.is_err());
+        assert!(contract
+            .set_project_status(id.clone(), "pending final approval".to_string())
+            .is_err());
+        set_signer("bob.testnet");
+        let result = contract.submit_work(
+            id.clone(),
+            "https://github.com/test-project/pull/1".to_string(),
+            "Done".to_string()
+        );
+        assert!(result.is_ok());
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.pending_final_approval.len(), 1);
+        let submissions = &projects.pending_final_approval[0].submissions;
+        assert_eq!(submissions.len(), 1);
+        assert_eq!(submissions[0].pull_request_url, "https://github.com/test-project/pull/1");
+    }
+
+    #[test]
+    // Rejecting a submission needs feedback and should send the job back to in progress
+    fn test_request_revision() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        for round in 1..3 {
+            set_signer("bob.testnet");
+            let result = contract.submit_work(
+                id.clone(),
+                format!("https://github.com/test-project/pull/{}", round),
+                "Done".to_string()
+            );
+            assert!(result.is_ok());
+            set_signer("alice.testnet");
+            assert!(contract.approve_submission(id.clone(), false, None).is_err());
+            assert!(contract
+                .approve_submission(id.clone(), false, Some(" ".to_string()))
+                .is_err());
+            assert!(contract
+                .approve_submission(id.clone(), false, Some(format!("Fix round {}", round)))
+                .is_ok());
+            let project = contract.find_project(&id).unwrap();
+            assert_eq!(project.status, Status::InProgress);
+            assert_eq!(project.revisions, round);
+        }
+        let thread = contract.get_submission_thread(id).unwrap();
+        assert_eq!(thread.len(), 2);
+        assert_eq!(thread[0].feedback, Some("Fix round 1".to_string()));
+        assert_eq!(thread[1].pull_request_url, "https://github.com/test-project/pull/2");
+        assert_eq!(thread[1].feedback, Some("Fix round 2".to_string()));
+    }
+
+    // Creates a job assigned to bob.testnet that is in progress
+    fn add_in_progress_project(contract: &mut ProjectManagement) -> String {
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        volunteer(contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        set_signer("bob.testnet");
+        assert!(contract
+            .set_project_status(id.clone(), "in progress".to_string())
+            .is_ok());
+        set_signer("alice.testnet");
+        id
+    }
+
+    #[test]
+    // A dispute needs an arbiter and only that arbiter can split the reward
+    fn test_resolve_dispute_with_split() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = add_in_progress_project(&mut contract);
+        set_signer("bob.testnet");
+        assert!(contract.open_dispute(id.clone(), "Not paid".to_string()).is_err());
+        set_signer("alice.testnet");
+        assert!(contract
+            .set_default_arbiter(Some("judge.testnet".to_string()))
+            .is_ok());
+        set_signer("carol.testnet");
+        assert!(contract.open_dispute(id.clone(), "Not paid".to_string()).is_err());
+        set_signer("bob.testnet");
+        assert_eq!(
+            contract.open_dispute(id.clone(), "Not paid".to_string()),
+            Ok("judge.testnet".to_string())
+        );
+        assert_eq!(
+            get_logs(),
+            vec![r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"dispute_opened","data":{"project_id":"1","opened_by":"bob.testnet","arbiter_id":"judge.testnet"}}"#]
+        );
+        assert_eq!(contract.get_all_projects(None, None).disputed.len(), 1);
+        assert!(contract
+            .set_project_status(id.clone(), "in progress".to_string())
+            .is_err());
+        assert!(contract.resolve_dispute(id.clone(), 50).is_err());
+        set_signer("judge.testnet");
+        assert!(contract.resolve_dispute(id.clone(), 101).is_err());
+        assert!(contract.resolve_dispute(id.clone(), 25).unwrap().is_some());
+        let project = contract.find_project(&id).unwrap();
+        assert_eq!(project.status, Status::Complete);
+        assert_eq!(project.dispute.unwrap().worker_percentage, Some(25));
+        let summary = contract.get_escrow_summary();
+        assert_eq!(summary.total_paid_out, U128(500));
+        assert_eq!(summary.total_refunded, U128(1500));
+        assert_eq!(summary.total_escrowed, U128(0));
+    }
+
+    #[test]
+    // Splitting a token reward close to u128::MAX should not overflow
+    fn test_resolve_dispute_with_large_reward() {
+        let mut context = get_context(vec![], false, "alice.testnet".to_string());
+        context.predecessor_account_id = "token.testnet".to_string();
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let msg = r#"{"id":"1","github_issue_link":"https://github.com/test-project/issues/1","description":"This is a test"}"#;
+        contract.ft_on_transfer("alice.testnet".to_string(), U128(u128::MAX), msg.to_string());
+        set_signer("alice.testnet");
+        assert!(contract
+            .set_default_arbiter(Some("judge.testnet".to_string()))
+            .is_ok());
+        volunteer(&mut contract, "1".to_string(), "bob.testnet");
+        assert!(contract.approve_user_for_project("1".to_string(), true).is_ok());
+        set_signer("bob.testnet");
+        assert!(contract
+            .set_project_status("1".to_string(), "in progress".to_string())
+            .is_ok());
+        assert!(contract.open_dispute("1".to_string(), "Not paid".to_string()).is_ok());
+        set_signer("judge.testnet");
+        assert!(contract.resolve_dispute("1".to_string(), 99).unwrap().is_some());
+        let project = contract.get_project(&"1".to_string()).unwrap();
+        assert_eq!(project.status, Status::Complete);
+    }
+
+    #[test]
+    // The company's share of a settled dispute should stay owed to it when the refund bounces
+    fn test_claim_refund_after_bounced_dispute_refund() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = add_in_progress_project(&mut contract);
+        assert!(contract
+            .set_default_arbiter(Some("judge.testnet".to_string()))
+            .is_ok());
+        set_signer("bob.testnet");
+        assert!(contract.open_dispute(id.clone(), "Not paid".to_string()).is_ok());
+        set_signer("judge.testnet");
+        assert!(contract.resolve_dispute(id.clone(), 25).is_ok());
+        set_promise_result(context.clone(), PromiseResult::Failed);
+        assert!(!contract.resolve_owner_refund(id.clone(), 1500));
+        let project = contract.get_project(&id).unwrap();
+        assert_eq!(project.status, Status::Complete);
+        assert_eq!(project.reward, 2000);
+        assert_eq!(project.unrefunded_amount, 1500);
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(1500));
+
+        set_signer("bob.testnet");
+        assert_eq!(contract.claim_refund(id.clone()).err(), Some(ProjectError::NotOwner));
+        set_signer("alice.testnet");
+        assert!(contract.claim_refund(id.clone()).is_ok());
+        assert_eq!(contract.claim_refund(id.clone()).err(), Some(ProjectError::NoFailedRefund));
+        set_promise_result(context, PromiseResult::Successful(vec![]));
+        assert!(contract.resolve_owner_refund(id.clone(), 1500));
+        assert_eq!(contract.get_project(&id).unwrap().unrefunded_amount, 0);
+        let summary = contract.get_escrow_summary();
+        assert_eq!(summary.total_escrowed, U128(0));
+        assert_eq!(summary.total_refunded, U128(1500));
+    }
+
+    #[test]
+    // An arbiter set on the project takes precedence over the default one
+    fn test_project_arbiter() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        assert!(contract
+            .set_default_arbiter(Some("judge.testnet".to_string()))
+            .is_ok());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(contract
+            .set_project_arbiter(id.clone(), "alice.testnet".to_string())
+            .is_err());
+        assert!(contract
+            .set_project_arbiter(id.clone(), "court.testnet".to_string())
+            .is_ok());
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        set_signer("bob.testnet");
+        assert!(contract
+            .set_project_status(id.clone(), "in progress".to_string())
+            .is_ok());
+        set_signer("alice.testnet");
+        assert_eq!(
+            contract.open_dispute(id.clone(), "Work is incomplete".to_string()),
+            Ok("court.testnet".to_string())
+        );
+        set_signer("court.testnet");
+        assert!(contract.resolve_dispute(id, 0).unwrap().is_some());
+        assert_eq!(contract.get_escrow_summary().total_refunded, U128(2000));
+    }
+
+    #[test]
+    // Applications close after apply_by and the company can reclaim the job after deliver_by
+    fn test_deadlines() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                Some(U64(100)),
+                Some(U64(200)),
+            )
+            .unwrap();
+        set_signer_at("bob.testnet", 50);
+        assert!(contract
+            .apply_to_project(id.clone(), "I can do it".to_string(), None)
+            .is_ok());
+        set_signer_at("carol.testnet", 150);
+        assert!(contract
+            .apply_to_project(id.clone(), "Me too".to_string(), None)
+            .is_err());
+        set_signer_at("alice.testnet", 150);
+        assert!(contract
+            .accept_application(id.clone(), "bob.testnet".to_string())
+            .is_ok());
+        assert!(contract.reclaim_project(id.clone()).is_err());
+        assert_eq!(contract.get_all_projects(None, None).not_started.len(), 1);
+        set_signer_at("alice.testnet", 250);
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.overdue.len(), 1);
+        assert_eq!(projects.not_started.len(), 0);
+        set_signer_at("bob.testnet", 250);
+        assert!(contract.reclaim_project(id.clone()).is_err());
+        set_signer_at("alice.testnet", 250);
+        assert!(contract.reclaim_project(id.clone()).is_ok());
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.created.len(), 1);
+        assert_eq!(projects.created[0].worker, None);
+        assert_eq!(projects.created[0].deliver_by, None);
+        let projects = contract.get_user_projects("bob.testnet".to_string(), None, None);
+        assert_eq!(projects.not_started.len(), 0);
+    }
+
+    #[test]
+    // The worker can claim the reward once the review window has passed without a review
+    fn test_claim_after_timeout() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        assert!(contract.set_review_window(U64(100)).is_ok());
+        let id = add_in_progress_project(&mut contract);
+        set_signer_at("bob.testnet", 1000);
+        assert!(contract.claim_after_timeout(id.clone()).is_err());
+        let result = contract.submit_work(
+            id.clone(),
+            "https://github.com/test-project/pull/1".to_string(),
+            "Done".to_string()
+        );
+        assert!(result.is_ok());
+        assert_eq!(contract.find_project(&id).unwrap().review_by, Some(U64(1100)));
+        set_signer_at("bob.testnet", 1050);
+        assert!(contract.claim_after_timeout(id.clone()).is_err());
+        // A new window only applies to work handed in after it was set
+        set_signer_at("alice.testnet", 1060);
+        assert!(contract.set_review_window(U64(u64::MAX)).is_ok());
+        set_signer_at("carol.testnet", 1100);
+        assert!(contract.claim_after_timeout(id.clone()).is_err());
+        set_signer_at("bob.testnet", 1100);
+        assert!(contract.claim_after_timeout(id.clone()).is_ok());
+        assert_eq!(contract.find_project(&id).unwrap().status, Status::Complete);
+        assert_eq!(contract.get_escrow_summary().total_paid_out, U128(2000));
+
+        // A window too large to add to the timestamp keeps the review open
+        set_signer("alice.testnet");
+        let id = contract
+            .add_project(
+                "2".to_string(),
+                "https://github.com/test-project/issues/2".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        set_signer_at("bob.testnet", 2000);
+        assert!(contract
+            .submit_work(
+                id.clone(),
+                "https://github.com/test-project/pull/2".to_string(),
+                "Done".to_string()
+            )
+            .is_ok());
+        assert_eq!(contract.find_project(&id).unwrap().review_by, Some(U64(u64::MAX)));
+        set_signer_at("bob.testnet", 3000);
+        assert_eq!(
+            contract.claim_after_timeout(id.clone()).err(),
+            Some(ProjectError::ReviewWindowOpen)
+        );
+    }
+
+    #[test]
+    // Every role-guarded method should refuse callers outside its role
+    fn test_denied_paths() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+
+        // Taking over the id of an existing job
+        set_signer_with_deposit("mallory.testnet", 1);
+        let message = assert_fails(|| {
+            contract.add_project(
+                id.clone(),
+                "https://github.com/test-project/issues/2".to_string(),
+                "Mine now".to_string(),
+                None,
+                None,
+            )
+        });
+        assert!(message.contains(&ProjectError::ProjectAlreadyExists.to_string()));
+        let project = contract.get_project(&id).unwrap();
+        assert_eq!(project.project_owner, "alice.testnet".to_string());
+        assert_eq!(project.reward, 2000);
+        assert_eq!(contract.total_escrowed, 2000);
+
+        // Company only methods on a job without a worker
+        set_signer("mallory.testnet");
+        let not_owner = Some(ProjectError::NotOwner);
+        assert_eq!(
+            contract
+                .update_project(id.clone(), None, Some("Mine now".to_string()), None, None)
+                .err(),
+            not_owner
+        );
+        let message = assert_fails(|| contract.increase_reward(id.clone()));
+        assert!(message.contains(&ProjectError::NotOwner.to_string()));
+        let message = assert_fails(|| contract.accept_bid(id.clone(), "bob.testnet".to_string()));
+        assert!(message.contains(&ProjectError::NotOwner.to_string()));
+        assert_eq!(
+            contract
+                .decrease_reward(id.clone(), "100".to_string())
+                .err(),
+            not_owner
+        );
+        assert_eq!(contract.set_milestones(id.clone(), vec![]).err(), not_owner);
+        assert_eq!(contract.remove_project(id.clone()).err(), not_owner);
+        assert_eq!(contract.set_bidding(id.clone(), true).err(), not_owner);
+        assert_eq!(
+            contract
+                .set_project_arbiter(id.clone(), "mallory.testnet".to_string())
+                .err(),
+            not_owner
+        );
+        assert_eq!(contract.claim_refund(id.clone()).err(), not_owner);
+        assert_eq!(
+            contract.set_user_for_project(id.clone(), "bob.testnet".to_string()),
+            Err(ProjectError::SelfAssignmentOnly)
+        );
+
+        // Admin only methods
+        assert_eq!(
+            contract.set_review_window(U64(0)),
+            Err(ProjectError::NotAdmin)
+        );
+        assert_eq!(
+            contract.set_default_arbiter(Some("mallory.testnet".to_string())),
+            Err(ProjectError::NotAdmin)
+        );
+
+        set_signer("bob.testnet");
+        assert!(contract
+            .apply_to_project(id.clone(), "I can do it".to_string(), None)
+            .is_ok());
+        set_signer("mallory.testnet");
+        assert_eq!(
+            contract.accept_application(id.clone(), "bob.testnet".to_string()),
+            Err(ProjectError::NotOwner)
+        );
+        set_signer("alice.testnet");
+        assert!(contract
+            .accept_application(id.clone(), "bob.testnet".to_string())
+            .is_ok());
+
+        // Methods on a job with a worker
+        set_signer("mallory.testnet");
+        assert_eq!(
+            contract.approve_user_for_project(id.clone(), false).err(),
+            not_owner
+        );
+        assert_eq!(contract.reclaim_project(id.clone()).err(), not_owner);
+        assert_eq!(contract.approve_milestone(id.clone(), 0).err(), not_owner);
+        assert_eq!(contract.set_project_complete(id.clone()).err(), not_owner);
+        assert_eq!(
+            contract.set_project_status(id.clone(), "in progress".to_string()),
+            Err(ProjectError::NotWorker)
+        );
+        assert_eq!(
+            contract.submit_work(
+                id.clone(),
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            ),
+            Err(ProjectError::NotWorker)
+        );
+        assert_eq!(
+            contract.submit_milestone(id.clone(), 0),
+            Err(ProjectError::NotWorker)
+        );
+        assert_eq!(
+            contract
+                .claim_payout(id.clone(), "mallory.testnet".to_string())
+                .err(),
+            Some(ProjectError::NotWorker)
+        );
+        assert_eq!(
+            contract.remove_user_from_project(id.clone(), "bob.testnet".to_string()),
+            Err(ProjectError::NotOwnerOrWorker)
+        );
+        assert_eq!(
+            contract.open_dispute(id.clone(), "Mine".to_string()),
+            Err(ProjectError::NotOwnerOrWorker)
+        );
+        assert_eq!(
+            contract.retry_payout(id.clone()).err(),
+            Some(ProjectError::NotOwnerOrWorker)
+        );
+        set_signer("bob.testnet");
+        assert_eq!(contract.set_project_complete(id.clone()).err(), not_owner);
+        assert_eq!(contract.reclaim_project(id.clone()).err(), not_owner);
+        assert!(contract
+            .submit_work(
+                id.clone(),
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            )
+            .is_ok());
+        set_signer("mallory.testnet");
+        assert_eq!(
+            contract.approve_submission(id.clone(), true, None).err(),
+            not_owner
+        );
+        assert_eq!(
+            contract.claim_after_timeout(id.clone()).err(),
+            Some(ProjectError::NotWorker)
+        );
+        set_signer("bob.testnet");
+        assert_eq!(
+            contract.approve_submission(id.clone(), true, None).err(),
+            not_owner
+        );
+
+        // Only the arbiter settles a dispute
+        set_signer("alice.testnet");
+        assert!(contract
+            .set_default_arbiter(Some("judge.testnet".to_string()))
+            .is_ok());
+        assert!(contract
+            .open_dispute(id.clone(), "Not good enough".to_string())
+            .is_ok());
+        for account in ["mallory.testnet", "alice.testnet", "bob.testnet"] {
+            set_signer(account);
+            assert_eq!(
+                contract.resolve_dispute(id.clone(), 100).err(),
+                Some(ProjectError::NotArbiter)
+            );
+        }
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.disputed.len(), 1);
+        assert_eq!(projects.disputed[0].description, "This is a test");
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(2000));
+    }
+
+    #[test]
+    // Status changes should follow the transition table
+    fn test_status_transitions() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            contract.get_allowed_transitions(id.clone()),
+            Ok(vec![Status::PendingWorkerApproval, Status::NotStarted])
+        );
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        assert!(contract.set_project_complete(id.clone()).is_err());
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        assert!(contract.approve_user_for_project(id.clone(), true).is_err());
+        set_signer("bob.testnet");
+        let result = contract.submit_work(
+            id.clone(),
+            "https://github.com/test-project/pull/1".to_string(),
+            "Done".to_string()
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            contract.get_allowed_transitions(id.clone()),
+            Ok(vec![Status::InProgress, Status::Complete, Status::Disputed])
+        );
+        let result = contract.set_project_status(id.clone(), "not started".to_string());
+        assert_eq!(
+            result,
+            Err(ProjectError::InvalidTransition {
+                from: Status::PendingFinalApproval,
+                to: Status::NotStarted,
+            })
+        );
+        assert_eq!(contract.find_project(&id).unwrap().status, Status::PendingFinalApproval);
+    }
+
+    #[test]
+    fn test_project_errors() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let missing = "missing".to_string();
+        assert_eq!(
+            contract.update_project(missing.clone(), None, None, None, None),
+            Err(ProjectError::ProjectNotFound)
+        );
+        assert_eq!(
+            contract.set_user_for_project(missing.clone(), "alice.testnet".to_string()),
+            Err(ProjectError::ProjectNotFound)
+        );
+        assert!(matches!(
+            contract.remove_project(missing.clone()),
+            Err(ProjectError::ProjectNotFound)
+        ));
+        assert_eq!(
+            contract.get_allowed_transitions(missing),
+            Err(ProjectError::ProjectNotFound)
+        );
+
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        volunteer(&mut contract, id.clone(), "bob.testnet");
+        set_signer("carol.testnet");
+        assert_eq!(
+            contract.set_user_for_project(id.clone(), "carol.testnet".to_string()),
+            Err(ProjectError::WorkerAlreadyAssigned)
+        );
+        assert_eq!(
+            contract.approve_user_for_project(id.clone(), true),
+            Err(ProjectError::NotOwner)
+        );
+
+        let error = serde_json::to_string(&ProjectError::NotOwner).unwrap();
+        assert_eq!(
+            error,
+            r#"{"code":"NOT_OWNER","message":"You can only edit projects you own."}"#
+        );
+    }
+
+    fn add_two_worker_projects(contract: &mut ProjectManagement) {
+        for i in 1..3 {
+            contract
+                .add_project(
+                    i.to_string(),
+                    "https://github.com/test-project/issues/1".to_string(),
+                    "This is a test".to_string(),
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        volunteer(contract, "1".to_string(), "bob.testnet");
+        volunteer(contract, "2".to_string(), "carol.testnet");
+    }
+
+    #[test]
+    fn test_worker_projects_are_isolated() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        add_two_worker_projects(&mut contract);
+
+        let bob = contract.get_user_projects("bob.testnet".to_string(), None, None);
+        assert_eq!(bob.pending_work_approval.len(), 1);
+        assert_eq!(bob.pending_work_approval[0].id, "1".to_string());
+        let carol = contract.get_user_projects("carol.testnet".to_string(), None, None);
+        assert_eq!(carol.pending_work_approval.len(), 1);
+        assert_eq!(carol.pending_work_approval[0].id, "2".to_string());
+
+        assert!(contract.approve_user_for_project("1".to_string(), false).is_ok());
+        let bob = contract.get_user_projects("bob.testnet".to_string(), None, None);
+        assert_eq!(bob.pending_work_approval.len(), 0);
+        let carol = contract.get_user_projects("carol.testnet".to_string(), None, None);
+        assert_eq!(carol.pending_work_approval.len(), 1);
+    }
+
+    #[test]
+    // Rebuilding the index should drop every id that does not belong to the worker
+    fn test_migrate_user_projects() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        add_two_worker_projects(&mut contract);
+
+        // Leave stale ids in the index: job "2" in bob's set and a set for a worker who left
+        let mut bob = contract.user_projects.get(&"bob.testnet".to_string()).unwrap();
+        bob.insert(&"2".to_string());
+        contract.user_projects.insert(&"bob.testnet".to_string(), &bob);
+        let mut dave = UnorderedSet::new(b"stale-dave".to_vec());
+        dave.insert(&"1".to_string());
+        contract.user_projects.insert(&"dave.testnet".to_string(), &dave);
+
+        contract.migrate_user_projects();
+        let bob = contract.get_user_projects("bob.testnet".to_string(), None, None);
+        assert_eq!(bob.pending_work_approval.len(), 1);
+        assert_eq!(bob.pending_work_approval[0].id, "1".to_string());
+        let carol = contract.get_user_projects("carol.testnet".to_string(), None, None);
+        assert_eq!(carol.pending_work_approval.len(), 1);
+        assert_eq!(carol.pending_work_approval[0].id, "2".to_string());
+        let dave = contract.get_user_projects("dave.testnet".to_string(), None, None);
+        assert!(dave.pending_work_approval.is_empty());
+        assert_eq!(contract.user_projects.len(), 2);
+    }
+
+    #[test]
+    fn test_user_projects_follow_project_changes() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        add_two_worker_projects(&mut contract);
+        assert!(contract
+            .update_project("1".to_string(), None, Some("Updated".to_string()), None, None)
+            .is_ok());
+        assert!(contract.increase_reward("1".to_string()).is_ok());
+        assert!(contract.approve_user_for_project("1".to_string(), true).is_ok());
+        assert!(contract.set_project_complete("1".to_string()).is_ok());
+
+        let project = contract.find_project(&"1".to_string()).unwrap();
+        let bob = contract.get_user_projects("bob.testnet".to_string(), None, None);
+        assert_eq!(bob.complete.len(), 1);
+        assert_eq!(bob.complete[0].status, project.status);
+        assert_eq!(bob.complete[0].description, "Updated".to_string());
+        assert_eq!(bob.complete[0].reward, 4000);
+    }
+
+    #[test]
+    fn test_get_owner_projects() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        add_two_worker_projects(&mut contract);
+        set_signer("dave.testnet");
+        contract
+            .add_project(
+                "3".to_string(),
+                "https://github.com/test-project/issues/3".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(contract.remove_project("3".to_string()).is_ok());
+        contract
+            .add_project(
+                "4".to_string(),
+                "https://github.com/test-project/issues/4".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+
+        let alice = contract.get_owner_projects("alice.testnet".to_string(), None, None);
+        assert_eq!(alice.pending_work_approval.len(), 2);
+        assert_eq!(alice.created.len(), 0);
+        let dave = contract.get_owner_projects("dave.testnet".to_string(), None, None);
+        assert_eq!(dave.created.len(), 1);
+        assert_eq!(dave.created[0].id, "4".to_string());
+        assert_eq!(dave.pending_work_approval.len(), 0);
+        let nobody = contract.get_owner_projects("nobody.testnet".to_string(), None, None);
+        assert_eq!(nobody.created.len(), 0);
+    }
+
+    #[test]
+    fn test_list_projects() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        add_two_worker_projects(&mut contract);
+        for (i, owner, deposit) in [(3, "dave.testnet", 5000), (4, "dave.testnet", 8000)] {
+            set_signer_with_deposit(owner, deposit);
+            contract
+                .add_project(
+                    i.to_string(),
+                    "https://github.com/test-project/issues/1".to_string(),
+                    "This is a test".to_string(),
+                    None,
+                    None,
+                )
+                .unwrap();
+        }
+        let mut context = get_context(vec![], false, "token.testnet".to_string());
+        context.storage_usage = env::storage_usage();
+        testing_env!(context);
+        let msg = r#"{"id":"5","github_issue_link":"https://github.com/test-project/issues/5","description":"This is a test"}"#;
+        contract.ft_on_transfer("erin.testnet".to_string(), U128(5000), msg.to_string());
+
+        // Walk every page with the cursor
+        let page = contract.list_projects(None, Some(U64(2)), None, None);
+        assert_eq!(page.projects.len(), 2);
+        assert_eq!(page.next_index, Some(U64(2)));
+        let page = contract.list_projects(page.next_index, Some(U64(2)), None, None);
+        assert_eq!(page.projects[0].id, "3".to_string());
+        assert_eq!(page.next_index, Some(U64(4)));
+        let page = contract.list_projects(page.next_index, Some(U64(2)), None, None);
+        assert_eq!(page.projects.len(), 1);
+        assert_eq!(page.next_index, None);
+
+        let filter = |filter: ProjectFilter| contract.list_projects(None, None, Some(filter), None).projects;
+        let ids = |projects: Vec<Project>| -> Vec<String> {
+            projects.into_iter().map(|project| project.id).collect()
+        };
+        assert_eq!(
+            ids(filter(ProjectFilter {
+                owner_id: Some("dave.testnet".to_string()),
+                ..Default::default()
+            })),
+            vec!["3", "4"]
+        );
+        assert_eq!(
+            ids(filter(ProjectFilter {
+                worker_id: Some("carol.testnet".to_string()),
+                ..Default::default()
+            })),
+            vec!["2"]
+        );
+        assert_eq!(
+            ids(filter(ProjectFilter {
+                status: Some(Status::Created),
+                min_reward: Some(U128(5000)),
+                max_reward: Some(U128(6000)),
+                ..Default::default()
+            })),
+            vec!["3", "5"]
+        );
+        assert_eq!(
+            ids(filter(ProjectFilter {
+                token_id: Some("token.testnet".to_string()),
+                ..Default::default()
+            })),
+            vec!["5"]
+        );
+
+        // The bucketed views page the same way
+        let projects = contract.get_all_projects(Some(U64(1)), Some(U64(3)));
+        assert_eq!(projects.pending_work_approval.len(), 1);
+        assert_eq!(projects.created.len(), 2);
+        assert_eq!(projects.next_index, Some(U64(4)));
+        let projects = contract.get_owner_projects("dave.testnet".to_string(), None, Some(U64(1)));
+        assert_eq!(projects.created.len(), 1);
+        assert_eq!(projects.next_index, Some(U64(1)));
+    }
+
+    #[test]
+    fn test_events() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context.clone());
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(contract
+            .update_project(id.clone(), None, Some("Updated".to_string()), None, None)
+            .is_ok());
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"project_created","data":{"project_id":"1","owner_id":"alice.testnet","reward":"2000","token_id":null}}"#,
+                r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"project_updated","data":{"project_id":"1","status":"Created"}}"#,
+            ]
+        );
+
+        set_signer("bob.testnet");
+        assert!(contract.set_user_for_project(id.clone(), "bob.testnet".to_string()).is_ok());
+        assert_eq!(
+            get_logs(),
+            vec![r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"worker_assigned","data":{"project_id":"1","worker_id":"bob.testnet","status":"PendingWorkerApproval"}}"#]
+        );
+
+        set_signer("alice.testnet");
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        assert_eq!(
+            get_logs(),
+            vec![r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"worker_approved","data":{"project_id":"1","worker_id":"bob.testnet"}}"#]
+        );
+
+        set_signer("bob.testnet");
+        assert!(contract
+            .submit_work(
+                id.clone(),
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            )
+            .is_ok());
+        assert_eq!(
+            get_logs(),
+            vec![r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"work_submitted","data":{"project_id":"1","worker_id":"bob.testnet","milestone":null}}"#]
+        );
+
+        set_signer("alice.testnet");
+        assert!(contract.approve_submission(id.clone(), true, None).is_ok());
+        assert_eq!(
+            get_logs(),
+            vec![r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"project_completed","data":{"project_id":"1","worker_id":"bob.testnet"}}"#]
+        );
+
+        set_promise_result(context, PromiseResult::Successful(vec![]));
+        assert!(contract.resolve_payout(id, "bob.testnet".to_string(), 2000));
+        assert_eq!(
+            get_logs(),
+            vec![r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"payout_sent","data":{"project_id":"1","receiver_id":"bob.testnet","amount":"2000"}}"#]
+        );
+
+        set_signer("alice.testnet");
+        let id = contract
+            .add_project(
+                "2".to_string(),
+                "https://github.com/test-project/issues/2".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(contract.remove_project(id).is_ok());
+        assert_eq!(
+            get_logs()[1],
+            r#"EVENT_JSON:{"standard":"project_management","version":"1.0.0","event":"project_removed","data":{"project_id":"2","owner_id":"alice.testnet"}}"#
+        );
+    }
+
+    #[test]
+    fn test_project_history() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        add_in_progress_project(&mut contract);
+        set_signer_at("bob.testnet", 500);
+        assert!(contract
+            .submit_work(
+                "1".to_string(),
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            )
+            .is_ok());
+        set_signer_at("alice.testnet", 900);
+        assert!(contract
+            .approve_submission("1".to_string(), false, Some("Add tests".to_string()))
+            .is_ok());
+
+        let history = contract.get_project_history("1".to_string(), None, None);
+        let actions: Vec<(&str, &str, Option<Status>, Status)> = history
+            .iter()
+            .map(|entry| {
+                (
+                    entry.action.as_str(),
+                    entry.actor.as_str(),
+                    entry.previous_status,
+                    entry.new_status,
+                )
+            })
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("project_created", "alice.testnet", None, Status::Created),
+                (
+                    "worker_assigned",
+                    "bob.testnet",
+                    Some(Status::Created),
+                    Status::PendingWorkerApproval
+                ),
+                (
+                    "worker_approved",
+                    "alice.testnet",
+                    Some(Status::PendingWorkerApproval),
+                    Status::NotStarted
+                ),
+                (
+                    "status_changed",
+                    "bob.testnet",
+                    Some(Status::NotStarted),
+                    Status::InProgress
+                ),
+                (
+                    "work_submitted",
+                    "bob.testnet",
+                    Some(Status::InProgress),
+                    Status::PendingFinalApproval
+                ),
+                (
+                    "revision_requested",
+                    "alice.testnet",
+                    Some(Status::PendingFinalApproval),
+                    Status::InProgress
+                ),
+            ]
+        );
+        assert_eq!(history[4].timestamp, U64(500));
+        assert_eq!(history[5].timestamp, U64(900));
+
+        let page = contract.get_project_history("1".to_string(), Some(U64(4)), Some(U64(1)));
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].action, "work_submitted".to_string());
+        assert!(contract
+            .get_project_history("1".to_string(), Some(U64(u64::MAX)), Some(U64(u64::MAX)))
+            .is_empty());
+        assert!(contract.get_project_history("2".to_string(), None, None).is_empty());
+    }
+
+    #[test]
+    fn test_project_timestamps() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+        set_signer_at("alice.testnet", 100);
+        let id = contract
+            .add_project(
+                "1".to_string(),
+                "https://github.com/test-project/issues/1".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        set_signer_at("bob.testnet", 200);
+        assert!(contract.set_user_for_project(id.clone(), "bob.testnet".to_string()).is_ok());
+        set_signer_at("alice.testnet", 300);
+        assert!(contract.approve_user_for_project(id.clone(), true).is_ok());
+        set_signer_at("bob.testnet", 400);
+        assert!(contract
+            .set_project_status(id.clone(), "in progress".to_string())
+            .is_ok());
+        set_signer_at("bob.testnet", 500);
+        assert!(contract
+            .submit_work(
+                id.clone(),
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            )
+            .is_ok());
+        set_signer_at("alice.testnet", 600);
+        assert!(contract.approve_submission(id.clone(), true, None).is_ok());
+
+        let project = contract.get_project(&id).unwrap();
+        assert_eq!(project.created_at, U64(100));
+        assert_eq!(project.assigned_at, Some(U64(200)));
+        assert_eq!(project.started_at, Some(U64(400)));
+        assert_eq!(project.submitted_at, Some(U64(500)));
+        assert_eq!(project.completed_at, Some(U64(600)));
+        assert_eq!(project.updated_at, U64(600));
+
+        set_signer_at("alice.testnet", 700);
+        contract
+            .add_project(
+                "2".to_string(),
+                "https://github.com/test-project/issues/2".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+        let sorted = |field: SortField, descending: bool| -> Vec<String> {
+            contract
+                .list_projects(None, None, None, Some(ProjectSort { field, descending }))
+                .projects
+                .into_iter()
+                .map(|project| project.id)
+                .collect()
+        };
+        assert_eq!(sorted(SortField::CreatedAt, true), vec!["2", "1"]);
+        assert_eq!(sorted(SortField::UpdatedAt, false), vec!["1", "2"]);
+        // Projects without the timestamp go last in both directions
+        assert_eq!(sorted(SortField::CompletedAt, false), vec!["1", "2"]);
+        assert_eq!(sorted(SortField::CompletedAt, true), vec!["1", "2"]);
+        // The sort covers every project, not just the ids of the page that was read
+        let newest_first = || Some(ProjectSort {
+            field: SortField::CreatedAt,
+            descending: true,
+        });
+        let page = contract.list_projects(None, Some(U64(1)), None, newest_first());
+        assert_eq!(page.projects[0].id, "2".to_string());
+        assert_eq!(page.next_index, Some(U64(1)));
+        let page = contract.list_projects(page.next_index, Some(U64(1)), None, newest_first());
+        assert_eq!(page.projects[0].id, "1".to_string());
+        assert_eq!(page.next_index, None);
+        assert!(contract
+            .list_projects(Some(U64(u64::MAX)), None, None, newest_first())
+            .projects
+            .is_empty());
+
+        // Handing in work that was never set in progress starts it too
+        set_signer_at("bob.testnet", 800);
+        assert!(contract.set_user_for_project("2".to_string(), "bob.testnet".to_string()).is_ok());
+        set_signer_at("alice.testnet", 900);
+        assert!(contract.approve_user_for_project("2".to_string(), true).is_ok());
+        set_signer_at("bob.testnet", 1000);
+        assert!(contract
+            .submit_work(
+                "2".to_string(),
+                "https://github.com/test-project/pull/2".to_string(),
+                "Done".to_string()
+            )
+            .is_ok());
+        let project = contract.get_project(&"2".to_string()).unwrap();
+        assert_eq!(project.started_at, Some(U64(1000)));
+        assert_eq!(project.submitted_at, Some(U64(1000)));
+    }
+
+    // Writes the state of the first deployed contract: alice's job "1" with bob working on it,
+    // job "2" that is still open and job "3" that bob already finished
+    fn write_v1_state() {
+        let mut project_ids = UnorderedSet::new(b"i");
+        let mut projects = LookupMap::new(b"p");
+        let mut user_ids = UnorderedSet::new(b"s");
+        let mut user_projects = LookupMap::new(b"u");
+        let jobs = vec![
+            ("1", StatusV1::InProgress, Some("bob.testnet".to_string())),
+            ("2", StatusV1::Created, None),
+            ("3", StatusV1::Complete, Some("bob.testnet".to_string())),
+        ];
+        for (id, status, worker) in jobs {
+            let id = id.to_string();
+            let project = ProjectV1 {
+                id: id.clone(),
+                github_issue_link: "https://github.com/test-project/issues/1".to_string(),
+                description: "This is a test".to_string(),
+                reward: 2000,
+                status,
+                worker: worker.clone(),
+                project_owner: "alice.testnet".to_string(),
+            };
+            project_ids.insert(&id);
+            projects.insert(&id, &project);
+            if let Some(worker) = worker {
+                // Every worker's map shared the b"p" prefix of `projects`
+                let worker_projects: LookupMap<String, ProjectV1> = LookupMap::new(b"p");
+                user_ids.insert(&id);
+                user_projects.insert(&worker, &worker_projects);
+            }
+        }
+        env::state_write(&ProjectManagementV1 {
+            project_ids,
+            projects,
+            user_ids,
+            user_projects,
+        });
+    }
+
+    #[test]
+    // The state of the first contract should be readable through every view after migrate
+    fn test_migrate_reads_the_first_contract_state() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        write_v1_state();
+
+        let mut contract = ProjectManagement::migrate();
+        assert_eq!(contract.get_state_version(), 2);
+        // The contract account was the admin before there was an owner
+        assert_eq!(contract.get_contract_owner(), "alice.testnet".to_string());
+        assert!(!contract.is_paused());
+        assert!(contract.projects.get(&"1".to_string()).is_none());
+        let project = contract.get_project(&"1".to_string()).unwrap();
+        assert_eq!(project.status, Status::InProgress);
+        assert_eq!(project.worker, Some("bob.testnet".to_string()));
+        assert_eq!(project.reward, 2000);
+        assert_eq!(project.created_at, U64(0));
+        assert_eq!(contract.get_project(&"3".to_string()).unwrap().paid_amount, 2000);
+
+        let projects = contract.get_all_projects(None, None);
+        assert_eq!(projects.created.len(), 1);
+        assert_eq!(projects.in_progress.len(), 1);
+        assert_eq!(projects.complete.len(), 1);
+        let projects = contract.get_user_projects("bob.testnet".to_string(), None, None);
+        assert_eq!(projects.in_progress.len(), 1);
+        assert_eq!(projects.complete.len(), 1);
+        let projects = contract.get_owner_projects("alice.testnet".to_string(), None, None);
+        assert_eq!(projects.created.len(), 1);
+        // Only the open jobs are still held in escrow
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(4000));
+
+        // Saving a project moves it to the versioned map
+        set_signer("bob.testnet");
+        assert!(contract
+            .submit_work(
+                "1".to_string(),
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            )
+            .is_ok());
+        assert!(contract.projects.get(&"1".to_string()).is_some());
+        assert!(contract.legacy_projects.get(&"1".to_string()).is_none());
+        assert_eq!(
+            contract.get_project(&"1".to_string()).unwrap().status,
+            Status::PendingFinalApproval
+        );
+        // The first entry of a migrated job starts from the status it already had
+        let history = contract.get_project_history("1".to_string(), None, None);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].previous_status, Some(Status::InProgress));
+
+        // A job that was never saved again is removed and refunded from the rebuilt ledger
+        set_signer("alice.testnet");
+        assert!(contract.remove_project("2".to_string()).is_ok());
+        assert!(contract.get_project(&"2".to_string()).is_err());
+        assert!(contract.legacy_projects.get(&"2".to_string()).is_none());
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(2000));
+    }
+
+    #[test]
+    // Running migrate on state that is already in the current layout should change nothing
+    fn test_migrate_keeps_state_in_the_current_layout() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("bob.testnet".to_string());
+        let id = add_in_progress_project(&mut contract);
+        env::state_write(&contract);
+
+        let contract = ProjectManagement::migrate();
+        assert_eq!(contract.get_state_version(), 2);
+        assert_eq!(contract.get_contract_owner(), "bob.testnet".to_string());
+        assert!(contract.projects.get(&id).is_some());
+        assert_eq!(contract.get_project(&id).unwrap().status, Status::InProgress);
+        assert_eq!(contract.get_escrow_summary().total_escrowed, U128(2000));
+    }
+
+    #[test]
+    // While paused only refunds and withdrawals should go through
+    fn test_pause_blocks_everything_but_refunds_and_withdrawals() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("admin.testnet".to_string());
+        let id = add_in_progress_project(&mut contract);
+        contract
+            .add_project(
+                "2".to_string(),
+                "https://github.com/test-project/issues/2".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(contract.pause(), Err(ProjectError::NotAdmin));
+        set_signer("admin.testnet");
+        assert!(contract.pause().is_ok());
+        assert!(contract.is_paused());
+        assert_eq!(contract.pause(), Err(ProjectError::ContractPaused));
+        assert!(get_logs()[0].contains("\"event\":\"contract_paused\""));
+
+        // Funding a new job fails the call so the deposit goes back
+        set_signer_with_deposit("alice.testnet", 3000);
+        let message = assert_fails(|| {
+            contract.add_project(
+                "3".to_string(),
+                "https://github.com/test-project/issues/3".to_string(),
+                "This is a test".to_string(),
+                None,
+                None,
+            )
+        });
+        assert!(message.contains(&ProjectError::ContractPaused.to_string()));
+        assert!(contract.get_project(&"3".to_string()).is_err());
+        assert_eq!(contract.total_escrowed, 4000);
+        set_signer("alice.testnet");
+        assert_eq!(
+            contract.update_project(id.clone(), None, Some("Changed".to_string()), None, None),
+            Err(ProjectError::ContractPaused)
+        );
+        set_signer("bob.testnet");
+        assert_eq!(
+            contract.submit_work(
+                id.clone(),
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            ),
+            Err(ProjectError::ContractPaused)
+        );
+        assert_eq!(contract.get_project(&id).unwrap().status, Status::InProgress);
+
+        // The company can still take its money back
+        set_signer("alice.testnet");
+        assert!(contract.decrease_reward("2".to_string(), "500".to_string()).is_ok());
+        assert!(contract.remove_project("2".to_string()).is_ok());
+
+        set_signer("admin.testnet");
+        assert!(contract.unpause().is_ok());
+        assert_eq!(contract.unpause(), Err(ProjectError::ContractNotPaused));
+        set_signer("bob.testnet");
+        assert!(contract
+            .submit_work(
+                id,
+                "https://github.com/test-project/pull/1".to_string(),
+                "Done".to_string()
+            )
+            .is_ok());
+    }
+
+    #[test]
+    // The contract should only change hands once the new owner accepts it
+    fn test_transfer_contract_ownership_needs_acceptance() {
+        let context = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context);
+        let mut contract = ProjectManagement::new("alice.testnet".to_string());
+
+        set_signer("bob.testnet");
+        assert_eq!(
+            contract.transfer_contract_ownership(Some("bob.testnet".to_string())),
+            Err(ProjectError::NotAdmin)
+        );
+        set_signer("alice.testnet");
+        assert!(contract
+            .transfer_contract_ownership(Some("bob.testnet".to_string()))
+            .is_ok());
+        // Nothing changes hands until the new owner accepts
+        assert_eq!(contract.get_contract_owner(), "alice.testnet".to_string());
+        assert!(contract.set_review_window(U64(100)).is_ok());
+
+        set_signer("carol.testnet");
+        assert_eq!(
+            contract.accept_contract_ownership(),
+            Err(ProjectError::NotPendingOwner)
+        );
+        set_signer("bob.testnet");
+        assert!(contract.accept_contract_ownership().is_ok());
+        assert_eq!(contract.get_contract_owner(), "bob.testnet".to_string());
+        assert_eq!(contract.get_pending_contract_owner(), None);
+        assert!(get_logs()[0].contains("\"event\":\"ownership_transferred\""));
+        assert!(contract.set_review_window(U64(200)).is_ok());
+
+        set_signer("alice.testnet");
+        assert_eq!(contract.set_review_window(U64(300)), Err(ProjectError::NotAdmin));
+        assert_eq!(contract.pause(), Err(ProjectError::NotAdmin));
+    }
 }
@@ -1,5 +1,6 @@
-use crate::structs::project::Project;
+use crate::structs::project::{Project, Status};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
 use near_sdk::serde::{Deserialize, Serialize};
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
@@ -11,6 +12,11 @@ pub struct AllProjectsReturn {
     pub(crate) pending_work_approval: Vec<Project>,
     pub(crate) created: Vec<Project>,
     pub(crate) pending_final_approval: Vec<Project>,
+    pub(crate) payout_failed: Vec<Project>,
+    pub(crate) disputed: Vec<Project>,
+    pub(crate) overdue: Vec<Project>,
+    // The from_index of the next page, or None once the last page has been read
+    pub(crate) next_index: Option<U64>,
 }
 
 impl AllProjectsReturn {
@@ -22,6 +28,34 @@ impl AllProjectsReturn {
             pending_work_approval: Vec::new(),
             created: Vec::new(),
             pending_final_approval: Vec::new(),
+            payout_failed: Vec::new(),
+            disputed: Vec::new(),
+            overdue: Vec::new(),
+            next_index: None,
         }
     }
+
+    // Adds the project to the bucket of its status. Overdue projects get a bucket of their own.
+    pub(crate) fn push(&mut self, project: Project) {
+        if project.is_overdue() {
+            self.overdue.push(project);
+            return;
+        }
+        match project.status {
+            Status::Complete => self.complete.push(project),
+            Status::InProgress => self.in_progress.push(project),
+            Status::Created => self.created.push(project),
+            Status::PendingWorkerApproval => self.pending_work_approval.push(project),
+            Status::NotStarted => self.not_started.push(project),
+            Status::PendingFinalApproval => self.pending_final_approval.push(project),
+            Status::PayoutFailed => self.payout_failed.push(project),
+            Status::Disputed => self.disputed.push(project),
+        }
+    }
+}
+
+impl Default for AllProjectsReturn {
+    fn default() -> Self {
+        Self::new()
+    }
 }
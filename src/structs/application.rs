@@ -0,0 +1,35 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ApplicationStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Application {
+    pub(crate) worker_id: AccountId,
+    pub(crate) pitch: String,
+    pub(crate) estimated_completion: Option<String>,
+    pub(crate) status: ApplicationStatus,
+}
+
+impl Application {
+    pub fn new(
+        worker_id: AccountId,
+        pitch: String,
+        estimated_completion: Option<String>,
+    ) -> Application {
+        Application {
+            worker_id,
+            pitch,
+            estimated_completion,
+            status: ApplicationStatus::Pending,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum BidStatus {
+    Pending,
+    // Accepted by the company but the bid is higher than the reward, so it needs a top up first
+    AwaitingTopUp,
+    Accepted,
+    Declined,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Bid {
+    pub(crate) worker_id: AccountId,
+    pub(crate) amount: u128, // Measured in the same unit as the project reward
+    pub(crate) timeline: String,
+    pub(crate) status: BidStatus,
+}
+
+impl Bid {
+    pub fn new(worker_id: AccountId, amount: u128, timeline: String) -> Bid {
+        Bid {
+            worker_id,
+            amount,
+            timeline,
+            status: BidStatus::Pending,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::structs::project::Status;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Dispute {
+    pub(crate) opened_by: AccountId,
+    pub(crate) reason: String,
+    pub(crate) arbiter: AccountId,
+    // The status the job was in when the dispute was opened
+    pub(crate) previous_status: Status,
+    // The share of the remaining reward the arbiter gave to the worker. The rest went to the company.
+    pub(crate) worker_percentage: Option<u8>,
+}
+
+impl Dispute {
+    pub fn new(
+        opened_by: AccountId,
+        reason: String,
+        arbiter: AccountId,
+        previous_status: Status,
+    ) -> Dispute {
+        Dispute {
+            opened_by,
+            reason,
+            arbiter,
+            previous_status,
+            worker_percentage: None,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnerEscrow {
+    pub(crate) owner_id: AccountId,
+    // Amounts are measured in yoctoNEAR. U128 is stored like u128 and is a string in JSON, so
+    // large amounts keep their precision in JavaScript.
+    pub(crate) escrowed: U128,
+    pub(crate) paid_out: U128,
+    pub(crate) refunded: U128,
+}
+
+impl OwnerEscrow {
+    pub fn new(owner_id: AccountId) -> OwnerEscrow {
+        OwnerEscrow {
+            owner_id,
+            escrowed: U128(0),
+            paid_out: U128(0),
+            refunded: U128(0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowSummary {
+    pub(crate) total_escrowed: U128,
+    pub(crate) total_paid_out: U128,
+    pub(crate) total_refunded: U128,
+    pub(crate) contract_balance: U128,
+    // True when the contract holds at least as much as it owes to open projects
+    pub(crate) solvent: bool,
+    pub(crate) owners: Vec<OwnerEscrow>,
+}
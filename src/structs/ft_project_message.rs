@@ -0,0 +1,13 @@
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+
+// The `msg` a company sends with `ft_transfer_call` to fund a new project
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtProjectMessage {
+    pub(crate) id: String,
+    pub(crate) github_issue_link: String,
+    pub(crate) description: String,
+    pub(crate) apply_by: Option<U64>,
+    pub(crate) deliver_by: Option<U64>,
+}
@@ -0,0 +1,35 @@
+use crate::structs::project::Status;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, AccountId};
+
+// One change to a project. `action` is the name of the event logged for it.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HistoryEntry {
+    pub(crate) action: String,
+    pub(crate) actor: AccountId,
+    pub(crate) timestamp: U64,
+    pub(crate) block_height: U64,
+    pub(crate) previous_status: Option<Status>,
+    pub(crate) new_status: Status,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        action: String,
+        actor: AccountId,
+        previous_status: Option<Status>,
+        new_status: Status,
+    ) -> HistoryEntry {
+        HistoryEntry {
+            action,
+            actor,
+            timestamp: U64(env::block_timestamp()),
+            block_height: U64(env::block_index()),
+            previous_status,
+            new_status,
+        }
+    }
+}
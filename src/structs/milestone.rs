@@ -0,0 +1,36 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Approved,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Milestone {
+    pub(crate) title: String,
+    pub(crate) amount: u128, // Measured in the same unit as the project reward
+    pub(crate) status: MilestoneStatus,
+}
+
+impl Milestone {
+    pub fn new(title: String, amount: u128) -> Milestone {
+        Milestone {
+            title,
+            amount,
+            status: MilestoneStatus::Pending,
+        }
+    }
+}
+
+// The shape the owner sends milestones in. The amount is a string like the other reward arguments.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MilestoneInput {
+    pub(crate) title: String,
+    pub(crate) amount: String,
+}
@@ -1,10 +1,16 @@
+use crate::structs::dispute::Dispute;
+use crate::structs::milestone::Milestone;
+use crate::structs::project_error::ProjectError;
+use crate::structs::submission::Submission;
+use crate::structs::transition::TRANSITIONS;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::AccountId;
+use near_sdk::{env, AccountId};
 
 near_sdk::setup_alloc!();
 
-#[derive(BorshDeserialize, BorshSerialize, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Serialize, Deserialize, Debug, Clone, Copy)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Status {
     Complete,
@@ -13,9 +19,11 @@ pub enum Status {
     NotStarted,
     PendingWorkerApproval,
     Created,
+    PayoutFailed,
+    Disputed,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Project {
     pub(crate) id: String,
@@ -24,7 +32,42 @@ pub struct Project {
     pub(crate) reward: u128, // The reward is measured in yoctoNEAR. One NEAR is 10**24 yoctoNEAR
     pub(crate) status: Status,
     pub(crate) worker: Option<AccountId>,
-    pub(crate) project_owner: AccountId
+    pub(crate) project_owner: AccountId,
+    // The NEP-141 token contract the reward is paid in. None means the reward is in NEAR.
+    pub(crate) token_id: Option<AccountId>,
+    // Optional ordered milestones. When set, their amounts add up to the reward and the reward
+    // is released one milestone at a time instead of by set_project_complete.
+    pub(crate) milestones: Vec<Milestone>,
+    // What has reached the worker so far and what bounced back and still has to be paid
+    pub(crate) paid_amount: u128,
+    pub(crate) unpaid_amount: u128,
+    // A refund to the company that bounced back and can be collected with claim_refund
+    pub(crate) unrefunded_amount: u128,
+    // When true workers bid on the job instead of applying for the posted reward
+    pub(crate) bidding: bool,
+    // Every piece of work the worker has handed in, oldest first
+    pub(crate) submissions: Vec<Submission>,
+    // How many times the company sent the work back for changes
+    pub(crate) revisions: u32,
+    // Rules on disputes for this job. When None the contract's default arbiter is used.
+    pub(crate) arbiter: Option<AccountId>,
+    pub(crate) dispute: Option<Dispute>,
+    // Block timestamps in nanoseconds. Applications close after apply_by and the work is overdue
+    // after deliver_by.
+    pub(crate) apply_by: Option<U64>,
+    pub(crate) deliver_by: Option<U64>,
+    // Block timestamps in nanoseconds of the project's progress. assigned_at and started_at are
+    // cleared when the worker leaves the job.
+    pub(crate) created_at: U64,
+    pub(crate) updated_at: U64,
+    pub(crate) assigned_at: Option<U64>,
+    pub(crate) started_at: Option<U64>,
+    // When the latest work was handed in. Starts the company's review window.
+    pub(crate) submitted_at: Option<U64>,
+    // When the review window of the latest work ends. It is fixed when the work is handed in, so
+    // changing the window does not move reviews already under way.
+    pub(crate) review_by: Option<U64>,
+    pub(crate) completed_at: Option<U64>,
 }
 
 impl Project {
@@ -33,7 +76,8 @@ impl Project {
         github_issue_link: String,
         description: String,
         reward: u128,
-        project_owner: AccountId
+        project_owner: AccountId,
+        token_id: Option<AccountId>,
     ) -> Project {
         Project {
             id,
@@ -42,7 +86,77 @@ impl Project {
             reward,
             status: Status::Created,
             worker: None,
-            project_owner
+            project_owner,
+            token_id,
+            milestones: Vec::new(),
+            paid_amount: 0,
+            unpaid_amount: 0,
+            unrefunded_amount: 0,
+            bidding: false,
+            submissions: Vec::new(),
+            revisions: 0,
+            arbiter: None,
+            dispute: None,
+            apply_by: None,
+            deliver_by: None,
+            created_at: U64(env::block_timestamp()),
+            updated_at: U64(env::block_timestamp()),
+            assigned_at: None,
+            started_at: None,
+            submitted_at: None,
+            review_by: None,
+            completed_at: None,
+        }
+    }
+
+    // Moves the project to `to` if the transition table allows it
+    pub(crate) fn transition(&mut self, to: Status) -> Result<(), ProjectError> {
+        if TRANSITIONS.contains(&(self.status, to)) {
+            let now = Some(U64(env::block_timestamp()));
+            match to {
+                Status::PendingWorkerApproval | Status::NotStarted
+                    if self.status == Status::Created =>
+                {
+                    self.assigned_at = now;
+                }
+                // Work can be handed in straight from NotStarted, which starts it as well
+                Status::InProgress | Status::PendingFinalApproval if self.started_at.is_none() => {
+                    self.started_at = now
+                }
+                Status::Complete if self.completed_at.is_none() => self.completed_at = now,
+                Status::Created => {
+                    self.assigned_at = None;
+                    self.started_at = None;
+                }
+                _ => {}
+            }
+            self.status = to;
+            Ok(())
+        } else {
+            Err(ProjectError::InvalidTransition {
+                from: self.status,
+                to,
+            })
+        }
+    }
+
+    pub(crate) fn is_accepting_applications(&self) -> bool {
+        match self.apply_by {
+            None => true,
+            Some(apply_by) => env::block_timestamp() <= apply_by.0,
+        }
+    }
+
+    // A job is overdue when its worker has not handed in the work by the deliver_by timestamp
+    pub(crate) fn is_overdue(&self) -> bool {
+        match self.deliver_by {
+            None => false,
+            Some(deliver_by) => {
+                env::block_timestamp() > deliver_by.0
+                    && (self.status == Status::PendingWorkerApproval
+                        || self.status == Status::NotStarted
+                        || self.status == Status::InProgress)
+            }
         }
     }
 }
@@ -0,0 +1,279 @@
+use crate::structs::project::Status;
+use near_sdk::serde::ser::{SerializeStruct, Serializer};
+use near_sdk::serde::Serialize;
+use near_sdk::{env, serde_json, AccountId};
+use std::fmt;
+
+// Every error a contract method can return. It is serialized as `{"code": ..., "message": ...}`
+// so clients can branch on the code, which never changes, and show the message to users.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProjectError {
+    ProjectNotFound,
+    ProjectAlreadyExists,
+    InvalidMessage,
+    NotOwner,
+    NotWorker,
+    NotOwnerOrWorker,
+    NotArbiter,
+    NotAdmin,
+    ContractPaused,
+    ContractNotPaused,
+    NotPendingOwner,
+    InvalidTransition { from: Status, to: Status },
+    InvalidStatus { status: String },
+    UseSubmitWork,
+    NotRemovable { status: Status },
+    WorkerAlreadyAssigned,
+    NoWorkerAssigned,
+    NotAssignedWorker { worker_id: AccountId },
+    SelfAssignmentOnly,
+    AwaitingWorkerApproval,
+    ProjectDisputed,
+    NotDisputed,
+    NotBeingWorkedOn,
+    NoWorkSubmitted,
+    FeedbackRequired,
+    ReviewWindowOpen,
+    NotOverdue,
+    InvalidAmount { amount: String },
+    ZeroAmount,
+    AmountExceedsReward,
+    DepositRequired,
+    RewardLocked,
+    TokenReward,
+    MilestonesDefined,
+    MilestonesLocked,
+    MilestoneTotalMismatch { total: u128, reward: u128 },
+    MilestoneNotFound { index: u64 },
+    MilestoneOutOfOrder { next: u64 },
+    MilestoneAlreadySubmitted,
+    MilestoneNotSubmitted,
+    NoMilestonesLeft,
+    PaidByMilestone,
+    NoFailedPayout,
+    NoFailedRefund,
+    OwnProject,
+    NotAcceptingApplications,
+    ApplicationDeadlinePassed,
+    BiddingOnly,
+    AlreadyApplied,
+    ApplicationNotFound { worker_id: AccountId },
+    NotBidding,
+    NotAcceptingBids,
+    AlreadyBid,
+    BidNotFound { worker_id: AccountId },
+    OwnerAsArbiter,
+    NoArbiter,
+    InvalidPercentage,
+}
+
+impl ProjectError {
+    // The stable code of the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            ProjectError::ProjectNotFound => "PROJECT_NOT_FOUND",
+            ProjectError::ProjectAlreadyExists => "PROJECT_ALREADY_EXISTS",
+            ProjectError::InvalidMessage => "INVALID_MESSAGE",
+            ProjectError::NotOwner => "NOT_OWNER",
+            ProjectError::NotWorker => "NOT_WORKER",
+            ProjectError::NotOwnerOrWorker => "NOT_OWNER_OR_WORKER",
+            ProjectError::NotArbiter => "NOT_ARBITER",
+            ProjectError::NotAdmin => "NOT_ADMIN",
+            ProjectError::ContractPaused => "CONTRACT_PAUSED",
+            ProjectError::ContractNotPaused => "CONTRACT_NOT_PAUSED",
+            ProjectError::NotPendingOwner => "NOT_PENDING_OWNER",
+            ProjectError::InvalidTransition { .. } => "INVALID_TRANSITION",
+            ProjectError::InvalidStatus { .. } => "INVALID_STATUS",
+            ProjectError::UseSubmitWork => "USE_SUBMIT_WORK",
+            ProjectError::NotRemovable { .. } => "NOT_REMOVABLE",
+            ProjectError::WorkerAlreadyAssigned => "WORKER_ALREADY_ASSIGNED",
+            ProjectError::NoWorkerAssigned => "NO_WORKER_ASSIGNED",
+            ProjectError::NotAssignedWorker { .. } => "NOT_ASSIGNED_WORKER",
+            ProjectError::SelfAssignmentOnly => "SELF_ASSIGNMENT_ONLY",
+            ProjectError::AwaitingWorkerApproval => "AWAITING_WORKER_APPROVAL",
+            ProjectError::ProjectDisputed => "PROJECT_DISPUTED",
+            ProjectError::NotDisputed => "NOT_DISPUTED",
+            ProjectError::NotBeingWorkedOn => "NOT_BEING_WORKED_ON",
+            ProjectError::NoWorkSubmitted => "NO_WORK_SUBMITTED",
+            ProjectError::FeedbackRequired => "FEEDBACK_REQUIRED",
+            ProjectError::ReviewWindowOpen => "REVIEW_WINDOW_OPEN",
+            ProjectError::NotOverdue => "NOT_OVERDUE",
+            ProjectError::InvalidAmount { .. } => "INVALID_AMOUNT",
+            ProjectError::ZeroAmount => "ZERO_AMOUNT",
+            ProjectError::AmountExceedsReward => "AMOUNT_EXCEEDS_REWARD",
+            ProjectError::DepositRequired => "DEPOSIT_REQUIRED",
+            ProjectError::RewardLocked => "REWARD_LOCKED",
+            ProjectError::TokenReward => "TOKEN_REWARD",
+            ProjectError::MilestonesDefined => "MILESTONES_DEFINED",
+            ProjectError::MilestonesLocked => "MILESTONES_LOCKED",
+            ProjectError::MilestoneTotalMismatch { .. } => "MILESTONE_TOTAL_MISMATCH",
+            ProjectError::MilestoneNotFound { .. } => "MILESTONE_NOT_FOUND",
+            ProjectError::MilestoneOutOfOrder { .. } => "MILESTONE_OUT_OF_ORDER",
+            ProjectError::MilestoneAlreadySubmitted => "MILESTONE_ALREADY_SUBMITTED",
+            ProjectError::MilestoneNotSubmitted => "MILESTONE_NOT_SUBMITTED",
+            ProjectError::NoMilestonesLeft => "NO_MILESTONES_LEFT",
+            ProjectError::PaidByMilestone => "PAID_BY_MILESTONE",
+            ProjectError::NoFailedPayout => "NO_FAILED_PAYOUT",
+            ProjectError::NoFailedRefund => "NO_FAILED_REFUND",
+            ProjectError::OwnProject => "OWN_PROJECT",
+            ProjectError::NotAcceptingApplications => "NOT_ACCEPTING_APPLICATIONS",
+            ProjectError::ApplicationDeadlinePassed => "APPLICATION_DEADLINE_PASSED",
+            ProjectError::BiddingOnly => "BIDDING_ONLY",
+            ProjectError::AlreadyApplied => "ALREADY_APPLIED",
+            ProjectError::ApplicationNotFound { .. } => "APPLICATION_NOT_FOUND",
+            ProjectError::NotBidding => "NOT_BIDDING",
+            ProjectError::NotAcceptingBids => "NOT_ACCEPTING_BIDS",
+            ProjectError::AlreadyBid => "ALREADY_BID",
+            ProjectError::BidNotFound { .. } => "BID_NOT_FOUND",
+            ProjectError::OwnerAsArbiter => "OWNER_AS_ARBITER",
+            ProjectError::NoArbiter => "NO_ARBITER",
+            ProjectError::InvalidPercentage => "INVALID_PERCENTAGE",
+        }
+    }
+
+    // Fails the whole call with the serialized error, so callers get the same code as from a
+    // returned error
+    pub(crate) fn panic(&self) -> ! {
+        env::panic(serde_json::to_string(self).unwrap_or_default().as_bytes())
+    }
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProjectError::ProjectNotFound => write!(f, "No project exists with this id."),
+            ProjectError::ProjectAlreadyExists => write!(f, "A project with this id already exists."),
+            ProjectError::InvalidMessage => {
+                write!(f, "The msg must contain an id, github_issue_link and description.")
+            }
+            ProjectError::NotOwner => write!(f, "You can only edit projects you own."),
+            ProjectError::NotWorker => write!(f, "Only the worker assigned to the job can do this."),
+            ProjectError::NotOwnerOrWorker => write!(f, "The worker or the company must request this."),
+            ProjectError::NotArbiter => write!(f, "Only the arbiter of this job can do this."),
+            ProjectError::NotAdmin => write!(f, "Only the contract admin can do this."),
+            ProjectError::ContractPaused => {
+                write!(f, "The contract is paused. Only refunds and withdrawals are allowed.")
+            }
+            ProjectError::ContractNotPaused => write!(f, "The contract is not paused."),
+            ProjectError::NotPendingOwner => {
+                write!(f, "Only the account the contract is being transferred to can accept it.")
+            }
+            ProjectError::InvalidTransition { from, to } => {
+                write!(f, "A job cannot move from {:?} to {:?}.", from, to)
+            }
+            ProjectError::InvalidStatus { status } => write!(f, "{} is not a valid value", status),
+            ProjectError::UseSubmitWork => {
+                write!(f, "Use submit_work to send the job for final approval.")
+            }
+            ProjectError::NotRemovable { status } => {
+                write!(f, "The job is {:?} and therefore cannot be removed.", status)
+            }
+            ProjectError::WorkerAlreadyAssigned => {
+                write!(f, "The job already has a user assigned to it.")
+            }
+            ProjectError::NoWorkerAssigned => write!(f, "The job has no user assigned to it."),
+            ProjectError::NotAssignedWorker { worker_id } => {
+                write!(f, "{} is not the worker on this job.", worker_id)
+            }
+            ProjectError::SelfAssignmentOnly => {
+                write!(f, "Workers can only put themselves forward for a job.")
+            }
+            ProjectError::AwaitingWorkerApproval => {
+                write!(f, "This job is pending a worker approval and cannot be changed")
+            }
+            ProjectError::ProjectDisputed => write!(
+                f,
+                "This job is disputed and cannot be changed until the arbiter rules on it"
+            ),
+            ProjectError::NotDisputed => write!(f, "This job is not disputed."),
+            ProjectError::NotBeingWorkedOn => {
+                write!(f, "Work can only be submitted while the job is being worked on.")
+            }
+            ProjectError::NoWorkSubmitted => {
+                write!(f, "The job has no work waiting for final approval.")
+            }
+            ProjectError::FeedbackRequired => write!(f, "Feedback is required to request a revision."),
+            ProjectError::ReviewWindowOpen => {
+                write!(f, "The company is still within its review window.")
+            }
+            ProjectError::NotOverdue => write!(f, "The job is not past its delivery deadline."),
+            ProjectError::InvalidAmount { amount } => write!(f, "{} is not a valid amount", amount),
+            ProjectError::ZeroAmount => write!(f, "The amount must be greater than zero."),
+            ProjectError::AmountExceedsReward => {
+                write!(f, "The amount cannot be more than the current reward.")
+            }
+            ProjectError::DepositRequired => {
+                write!(f, "You must attach the amount to add to the reward.")
+            }
+            ProjectError::RewardLocked => {
+                write!(f, "The reward of this job has been paid out and can no longer be changed.")
+            }
+            ProjectError::TokenReward => write!(
+                f,
+                "This job is paid in a fungible token and cannot be topped up with NEAR."
+            ),
+            ProjectError::MilestonesDefined => {
+                write!(f, "Clear the milestones of this job before changing its reward.")
+            }
+            ProjectError::MilestonesLocked => write!(
+                f,
+                "Milestones are fixed once a worker is assigned or work on them has been submitted."
+            ),
+            ProjectError::MilestoneTotalMismatch { total, reward } => write!(
+                f,
+                "The milestone amounts add up to {} but the reward is {}.",
+                total, reward
+            ),
+            ProjectError::MilestoneNotFound { index } => {
+                write!(f, "{} is not a valid milestone", index)
+            }
+            ProjectError::MilestoneOutOfOrder { next } => {
+                write!(f, "Milestone {} is the next one to submit.", next)
+            }
+            ProjectError::MilestoneAlreadySubmitted => {
+                write!(f, "This milestone is already awaiting approval.")
+            }
+            ProjectError::MilestoneNotSubmitted => write!(f, "This milestone has not been submitted."),
+            ProjectError::NoMilestonesLeft => write!(f, "This job has no milestones left to submit."),
+            ProjectError::PaidByMilestone => {
+                write!(f, "This job is paid by milestone. Use its milestones instead.")
+            }
+            ProjectError::NoFailedPayout => write!(f, "This job has no failed payout."),
+            ProjectError::NoFailedRefund => write!(f, "This job has no failed refund."),
+            ProjectError::OwnProject => write!(f, "You cannot apply or bid on your own job."),
+            ProjectError::NotAcceptingApplications => {
+                write!(f, "This job is no longer accepting applications.")
+            }
+            ProjectError::ApplicationDeadlinePassed => {
+                write!(f, "The application deadline for this job has passed.")
+            }
+            ProjectError::BiddingOnly => write!(f, "This job takes bids instead of applications."),
+            ProjectError::AlreadyApplied => write!(f, "You have already applied to this job."),
+            ProjectError::ApplicationNotFound { worker_id } => {
+                write!(f, "{} has no pending application for this job.", worker_id)
+            }
+            ProjectError::NotBidding => write!(f, "This job does not take bids."),
+            ProjectError::NotAcceptingBids => write!(f, "This job is no longer accepting bids."),
+            ProjectError::AlreadyBid => write!(f, "You have already bid on this job."),
+            ProjectError::BidNotFound { worker_id } => {
+                write!(f, "{} has no pending bid for this job.", worker_id)
+            }
+            ProjectError::OwnerAsArbiter => {
+                write!(f, "The company cannot be the arbiter of its own job.")
+            }
+            ProjectError::NoArbiter => write!(f, "There is no independent arbiter for this job."),
+            ProjectError::InvalidPercentage => {
+                write!(f, "The worker percentage must be between 0 and 100.")
+            }
+        }
+    }
+}
+
+impl Serialize for ProjectError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("ProjectError", 2)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
@@ -0,0 +1,214 @@
+use crate::structs::project::{Project, Status};
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::{env, serde_json, AccountId};
+
+pub const EVENT_STANDARD: &str = "project_management";
+// Bump whenever an event or the fields of its data change
+pub const EVENT_VERSION: &str = "1.0.0";
+
+// NEP-297 events logged on every change to a project, so indexers can follow the board without
+// polling the views
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum ProjectEvent {
+    ProjectCreated {
+        project_id: String,
+        owner_id: AccountId,
+        reward: U128,
+        token_id: Option<AccountId>,
+    },
+    ProjectUpdated {
+        project_id: String,
+        status: Status,
+    },
+    StatusChanged {
+        project_id: String,
+        status: Status,
+    },
+    RewardChanged {
+        project_id: String,
+        reward: U128,
+    },
+    BiddingChanged {
+        project_id: String,
+        bidding: bool,
+    },
+    ArbiterSet {
+        project_id: String,
+        arbiter_id: AccountId,
+    },
+    MilestonesSet {
+        project_id: String,
+        milestones: u64,
+    },
+    WorkerAssigned {
+        project_id: String,
+        worker_id: AccountId,
+        status: Status,
+    },
+    WorkerApproved {
+        project_id: String,
+        worker_id: AccountId,
+    },
+    WorkerRejected {
+        project_id: String,
+        worker_id: AccountId,
+    },
+    WorkerRemoved {
+        project_id: String,
+        worker_id: AccountId,
+    },
+    ProjectReclaimed {
+        project_id: String,
+        worker_id: Option<AccountId>,
+    },
+    WorkSubmitted {
+        project_id: String,
+        worker_id: AccountId,
+        milestone: Option<u64>,
+    },
+    RevisionRequested {
+        project_id: String,
+        worker_id: AccountId,
+        revisions: u32,
+    },
+    MilestoneApproved {
+        project_id: String,
+        worker_id: AccountId,
+        milestone: u64,
+    },
+    DisputeOpened {
+        project_id: String,
+        opened_by: AccountId,
+        arbiter_id: AccountId,
+    },
+    ProjectCompleted {
+        project_id: String,
+        worker_id: AccountId,
+    },
+    PayoutSent {
+        project_id: String,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    PayoutFailed {
+        project_id: String,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    PayoutRetried {
+        project_id: String,
+        receiver_id: AccountId,
+        amount: U128,
+    },
+    RefundFailed {
+        project_id: String,
+        owner_id: AccountId,
+        amount: U128,
+    },
+    RefundClaimed {
+        project_id: String,
+        owner_id: AccountId,
+        amount: U128,
+    },
+    ProjectRemoved {
+        project_id: String,
+        owner_id: AccountId,
+    },
+    ContractPaused {
+        owner_id: AccountId,
+    },
+    ContractUnpaused {
+        owner_id: AccountId,
+    },
+    OwnershipTransferred {
+        previous_owner_id: AccountId,
+        new_owner_id: AccountId,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a> {
+    standard: &'static str,
+    version: &'static str,
+    #[serde(flatten)]
+    event: &'a ProjectEvent,
+}
+
+impl ProjectEvent {
+    pub(crate) fn created(project: &Project) -> ProjectEvent {
+        ProjectEvent::ProjectCreated {
+            project_id: project.id.clone(),
+            owner_id: project.project_owner.clone(),
+            reward: U128(project.reward),
+            token_id: project.token_id.clone(),
+        }
+    }
+
+    pub(crate) fn updated(project: &Project) -> ProjectEvent {
+        ProjectEvent::ProjectUpdated {
+            project_id: project.id.clone(),
+            status: project.status,
+        }
+    }
+
+    pub(crate) fn reward_changed(project: &Project) -> ProjectEvent {
+        ProjectEvent::RewardChanged {
+            project_id: project.id.clone(),
+            reward: U128(project.reward),
+        }
+    }
+
+    pub(crate) fn refund_failed(project: &Project, amount: u128) -> ProjectEvent {
+        ProjectEvent::RefundFailed {
+            project_id: project.id.clone(),
+            owner_id: project.project_owner.clone(),
+            amount: U128(amount),
+        }
+    }
+
+    // The event name as it appears in the log
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ProjectEvent::ProjectCreated { .. } => "project_created",
+            ProjectEvent::ProjectUpdated { .. } => "project_updated",
+            ProjectEvent::StatusChanged { .. } => "status_changed",
+            ProjectEvent::RewardChanged { .. } => "reward_changed",
+            ProjectEvent::BiddingChanged { .. } => "bidding_changed",
+            ProjectEvent::ArbiterSet { .. } => "arbiter_set",
+            ProjectEvent::MilestonesSet { .. } => "milestones_set",
+            ProjectEvent::WorkerAssigned { .. } => "worker_assigned",
+            ProjectEvent::WorkerApproved { .. } => "worker_approved",
+            ProjectEvent::WorkerRejected { .. } => "worker_rejected",
+            ProjectEvent::WorkerRemoved { .. } => "worker_removed",
+            ProjectEvent::ProjectReclaimed { .. } => "project_reclaimed",
+            ProjectEvent::WorkSubmitted { .. } => "work_submitted",
+            ProjectEvent::RevisionRequested { .. } => "revision_requested",
+            ProjectEvent::MilestoneApproved { .. } => "milestone_approved",
+            ProjectEvent::DisputeOpened { .. } => "dispute_opened",
+            ProjectEvent::ProjectCompleted { .. } => "project_completed",
+            ProjectEvent::PayoutSent { .. } => "payout_sent",
+            ProjectEvent::PayoutFailed { .. } => "payout_failed",
+            ProjectEvent::PayoutRetried { .. } => "payout_retried",
+            ProjectEvent::RefundFailed { .. } => "refund_failed",
+            ProjectEvent::RefundClaimed { .. } => "refund_claimed",
+            ProjectEvent::ProjectRemoved { .. } => "project_removed",
+            ProjectEvent::ContractPaused { .. } => "contract_paused",
+            ProjectEvent::ContractUnpaused { .. } => "contract_unpaused",
+            ProjectEvent::OwnershipTransferred { .. } => "ownership_transferred",
+        }
+    }
+
+    pub(crate) fn emit(&self) {
+        let log = EventLog {
+            standard: EVENT_STANDARD,
+            version: EVENT_VERSION,
+            event: self,
+        };
+        let json = serde_json::to_string(&log).unwrap_or_default();
+        env::log(format!("EVENT_JSON:{}", json).as_bytes());
+    }
+}
@@ -0,0 +1,41 @@
+use crate::structs::project::{Project, Status};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+// Narrows down list_projects. Every field that is set has to match.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProjectFilter {
+    pub(crate) status: Option<Status>,
+    pub(crate) owner_id: Option<AccountId>,
+    pub(crate) worker_id: Option<AccountId>,
+    pub(crate) min_reward: Option<U128>,
+    pub(crate) max_reward: Option<U128>,
+    pub(crate) token_id: Option<AccountId>,
+}
+
+impl ProjectFilter {
+    pub(crate) fn matches(&self, project: &Project) -> bool {
+        self.status.is_none_or(|status| project.status == status)
+            && self
+                .owner_id
+                .as_ref()
+                .is_none_or(|owner_id| &project.project_owner == owner_id)
+            && self
+                .worker_id
+                .as_ref()
+                .is_none_or(|worker_id| project.worker.as_ref() == Some(worker_id))
+            && self
+                .min_reward
+                .is_none_or(|min_reward| project.reward >= min_reward.0)
+            && self
+                .max_reward
+                .is_none_or(|max_reward| project.reward <= max_reward.0)
+            && self
+                .token_id
+                .as_ref()
+                .is_none_or(|token_id| project.token_id.as_ref() == Some(token_id))
+    }
+}
@@ -1,12 +1,60 @@
 use crate::structs::all_projects_return::AllProjectsReturn;
+use crate::structs::application::{Application, ApplicationStatus};
+use crate::structs::bid::{Bid, BidStatus};
+use crate::structs::dispute::Dispute;
+use crate::structs::escrow_summary::{EscrowSummary, OwnerEscrow};
+use crate::structs::ft_project_message::FtProjectMessage;
+use crate::structs::history_entry::HistoryEntry;
+use crate::structs::milestone::{Milestone, MilestoneInput, MilestoneStatus};
 use crate::structs::project::{Project, Status};
+use crate::structs::project_error::ProjectError;
+use crate::structs::project_event::ProjectEvent;
+use crate::structs::project_filter::ProjectFilter;
+use crate::structs::project_management_v1::ProjectManagementV1;
+use crate::structs::project_page::ProjectPage;
+use crate::structs::project_sort::ProjectSort;
+use crate::structs::project_v1::ProjectV1;
+use crate::structs::role::Role;
+use crate::structs::transition::allowed_transitions;
+use crate::structs::submission::Submission;
 use crate::structs::user_project_returns::UserProjectsReturn;
+use crate::structs::versioned_project::VersionedProject;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::serde::{Serialize};
-use near_sdk::collections::{LookupMap, UnorderedSet};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::json_types::{U128, U64};
 use near_sdk::near_bindgen;
 use near_sdk::PanicOnDefault;
-use near_sdk::{env, AccountId, Promise};
+use near_sdk::{env, ext_contract, serde_json, AccountId, Gas, Promise, PromiseOrValue, PromiseResult};
+
+// Two weeks in nanoseconds
+const DEFAULT_REVIEW_WINDOW: u64 = 14 * 24 * 60 * 60 * 1_000_000_000;
+const GAS_FOR_RESOLVE_TRANSFER: Gas = 10_000_000_000_000;
+const GAS_FOR_FT_TRANSFER: Gas = 10_000_000_000_000;
+// ft_transfer requires exactly one yoctoNEAR to be attached
+const ONE_YOCTO: u128 = 1;
+// The first contract kept user_projects under b"u" and gave every worker's map the b"p" prefix of
+// `projects`. migrate builds the index under this prefix instead.
+const USER_PROJECTS_PREFIX: &[u8] = b"x";
+// How many projects a listing returns when no limit is given, and the most it will return
+const DEFAULT_PAGE_LIMIT: u64 = 50;
+const MAX_PAGE_LIMIT: u64 = 100;
+// Layout of the contract state. Bump it, and teach `migrate` the old layout, whenever a released
+// contract changes the fields of ProjectManagement. 1 is the first deployed contract.
+const STATE_VERSION: u32 = 2;
+
+#[ext_contract(ext_ft)]
+pub trait FungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+#[ext_contract(ext_self)]
+pub trait ProjectManagementCallbacks {
+    fn resolve_project_refund(&mut self, project: Project) -> bool;
+    fn resolve_reward_refund(&mut self, id: String, amount: u128) -> bool;
+    fn resolve_owner_refund(&mut self, id: String, amount: u128) -> bool;
+    fn resolve_payout(&mut self, id: String, receiver_id: AccountId, amount: u128) -> bool;
+}
 
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -18,24 +66,166 @@ pub enum ApproveReturn {
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct ProjectManagement {
-    // The reason for user_projects and user_ids is to reduce the loop time when getting/removing user_projects
+    // Every project id, so the projects can be listed. `projects` cannot be iterated.
     pub(crate) project_ids: UnorderedSet<String>,
-    pub(crate) projects: LookupMap<String, Project>,
-    // The reason for user_projects and user_ids is to reduce the loop time when getting/removing user_projects
-    pub(crate) user_ids: UnorderedSet<String>,
-    pub(crate) user_projects: LookupMap<AccountId, LookupMap<String, Project>>,
+    pub(crate) projects: LookupMap<String, VersionedProject>,
+    // Ids of removed projects whose refund has not come back yet. They stay taken until it does,
+    // since a bounced refund puts the project back.
+    pub(crate) pending_removals: LookupSet<String>,
+    // The ids of each company's jobs
+    pub(crate) owner_projects: LookupMap<AccountId, UnorderedSet<String>>,
+    // The ids of each worker's jobs. The jobs themselves are only stored in `projects`.
+    pub(crate) user_projects: UnorderedMap<AccountId, UnorderedSet<String>>,
+    // Running total of the yoctoNEAR the contract owes to open projects. Token rewards are not included.
+    pub(crate) total_escrowed: u128,
+    pub(crate) escrow_ledger: UnorderedMap<AccountId, OwnerEscrow>,
+    pub(crate) applications: LookupMap<String, Vec<Application>>,
+    pub(crate) bids: LookupMap<String, Vec<Bid>>,
+    // Rules on disputes for projects that have no arbiter of their own
+    pub(crate) default_arbiter: Option<AccountId>,
+    // How long the company has to review submitted work before the worker can claim the reward
+    pub(crate) review_window: u64,
+    // Every change made to each project, oldest first. Entries are only ever appended.
+    pub(crate) history: LookupMap<String, Vector<HistoryEntry>>,
+    // Projects written by the first contract. Each one moves to `projects` the next time it is
+    // saved.
+    pub(crate) legacy_projects: LookupMap<String, ProjectV1>,
+    pub(crate) state_version: u32,
+    // The admin of the contract, who sets contract-wide rules and can pause it
+    pub(crate) owner_id: AccountId,
+    // Set while an ownership transfer waits for the new owner to accept it
+    pub(crate) pending_owner_id: Option<AccountId>,
+    // While paused only refunds and withdrawals go through
+    pub(crate) paused: bool,
 }
 
 #[near_bindgen]
 impl ProjectManagement {
     #[init]
-    pub fn new() -> Self {
+    pub fn new(owner_id: AccountId) -> Self {
         Self {
             project_ids: UnorderedSet::new(b"i"),
-            projects: LookupMap::new(b"p"),
-            user_ids: UnorderedSet::new(b"s"),
-            user_projects: LookupMap::new(b"u"),
+            projects: LookupMap::new(b"v"),
+            pending_removals: LookupSet::new(b"r"),
+            owner_projects: LookupMap::new(b"o"),
+            user_projects: UnorderedMap::new(USER_PROJECTS_PREFIX),
+            total_escrowed: 0,
+            escrow_ledger: UnorderedMap::new(b"e"),
+            applications: LookupMap::new(b"a"),
+            bids: LookupMap::new(b"b"),
+            default_arbiter: None,
+            review_window: DEFAULT_REVIEW_WINDOW,
+            history: LookupMap::new(b"h"),
+            legacy_projects: LookupMap::new(b"p"),
+            state_version: STATE_VERSION,
+            owner_id,
+            pending_owner_id: None,
+            paused: false,
+        }
+    }
+
+    // Upgrades the state of the first contract to the current layout. The projects are left
+    // where they are and are read from the old map until they are next saved. The indexes and the
+    // escrow ledger, which the first contract did not keep, are built from them, and the contract
+    // account becomes the owner. Running it again on migrated state does nothing.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let state = env::storage_read(b"STATE").expect("The contract is not initialized");
+        if let Ok(current) = Self::try_from_slice(&state) {
+            if current.state_version == STATE_VERSION {
+                return current;
+            }
+        }
+        let old = ProjectManagementV1::try_from_slice(&state)
+            .unwrap_or_else(|_| env::panic(b"Cannot deserialize the contract state"));
+        let mut contract = Self::new(env::current_account_id());
+        contract.project_ids = old.project_ids;
+        // The ids of the jobs with a worker are no longer kept
+        let mut user_ids = old.user_ids;
+        user_ids.clear();
+        contract.legacy_projects = old.projects;
+        for id in contract.project_ids.to_vec() {
+            if let Some(project) = contract.find_project(&id) {
+                contract.add_owner_project(&project.project_owner, &id);
+                if let Some(worker_id) = &project.worker {
+                    contract.add_user_project(worker_id, &id);
+                }
+                if project.status != Status::Complete {
+                    contract.record_deposit(&project, project.reward);
+                }
+            }
+        }
+        contract
+    }
+
+    pub fn get_state_version(&self) -> u32 {
+        self.state_version
+    }
+
+    // Stops everything but refunds and withdrawals, for use during incidents
+    pub fn pause(&mut self) -> Result<(), ProjectError> {
+        self.authorize_admin()?;
+        if self.paused {
+            return Err(ProjectError::ContractPaused);
+        }
+        self.paused = true;
+        ProjectEvent::ContractPaused {
+            owner_id: self.owner_id.clone(),
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn unpause(&mut self) -> Result<(), ProjectError> {
+        self.authorize_admin()?;
+        if !self.paused {
+            return Err(ProjectError::ContractNotPaused);
+        }
+        self.paused = false;
+        ProjectEvent::ContractUnpaused {
+            owner_id: self.owner_id.clone(),
         }
+        .emit();
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    // Offers the contract to a new owner, who has to accept it before it changes hands. Passing
+    // None cancels a pending transfer.
+    pub fn transfer_contract_ownership(
+        &mut self,
+        new_owner_id: Option<AccountId>,
+    ) -> Result<(), ProjectError> {
+        self.authorize_admin()?;
+        self.pending_owner_id = new_owner_id;
+        Ok(())
+    }
+
+    pub fn accept_contract_ownership(&mut self) -> Result<(), ProjectError> {
+        let caller = env::predecessor_account_id();
+        if self.pending_owner_id.as_ref() != Some(&caller) {
+            return Err(ProjectError::NotPendingOwner);
+        }
+        let previous_owner_id = std::mem::replace(&mut self.owner_id, caller);
+        self.pending_owner_id = None;
+        ProjectEvent::OwnershipTransferred {
+            previous_owner_id,
+            new_owner_id: self.owner_id.clone(),
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn get_contract_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    pub fn get_pending_contract_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
     }
 
     #[payable]
@@ -44,154 +234,736 @@ impl ProjectManagement {
         id: String,
         github_issue_link: String,
         description: String,
-    ) -> Result<String, String> {
-        self.projects.insert(
-            &id,
-            &Project::new(
-                id.clone(),
-                github_issue_link,
-                description,
-                env::attached_deposit() as u128,
-                env::signer_account_id()
-            ),
+        apply_by: Option<U64>,
+        deliver_by: Option<U64>,
+    ) -> Result<String, ProjectError> {
+        // Both checks fail the call so the deposit goes back. Replacing a project would strand
+        // its reward.
+        if let Err(error) = self.ensure_not_paused() {
+            error.panic();
+        }
+        if self.is_id_taken(&id) {
+            ProjectError::ProjectAlreadyExists.panic();
+        }
+        let mut project = Project::new(
+            id.clone(),
+            github_issue_link,
+            description,
+            env::attached_deposit() as u128,
+            env::predecessor_account_id(),
+            None,
         );
+        project.apply_by = apply_by;
+        project.deliver_by = deliver_by;
+        self.record_deposit(&project, project.reward);
+        self.save_project(&mut project);
         self.project_ids.insert(&id);
+        self.add_owner_project(&project.project_owner, &id);
+        self.clear_history(&id);
+        self.record(&project, None, ProjectEvent::created(&project));
         Ok(id)
     }
 
+    // NEP-141 receiver. A company funds a project by calling `ft_transfer_call` on the token
+    // contract with a `msg` holding the project id, github issue link and description.
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        let message: FtProjectMessage = match serde_json::from_str(&msg) {
+            Ok(message) => message,
+            Err(_) => ProjectError::InvalidMessage.panic(),
+        };
+        if let Err(error) = self.ensure_not_paused() {
+            error.panic();
+        }
+        if self.is_id_taken(&message.id) {
+            ProjectError::ProjectAlreadyExists.panic();
+        }
+        let mut project = Project::new(
+            message.id.clone(),
+            message.github_issue_link,
+            message.description,
+            amount.0,
+            sender_id,
+            Some(env::predecessor_account_id()),
+        );
+        project.apply_by = message.apply_by;
+        project.deliver_by = message.deliver_by;
+        self.save_project(&mut project);
+        self.project_ids.insert(&message.id);
+        self.add_owner_project(&project.project_owner, &message.id);
+        self.clear_history(&message.id);
+        self.record(&project, None, ProjectEvent::created(&project));
+        // The whole amount is kept in escrow, so nothing is returned to the sender
+        PromiseOrValue::Value(U128(0))
+    }
+
     pub fn update_project(
         &mut self,
         id: String,
         github_issue_link: Option<String>,
         description: Option<String>,
-        reward: Option<String>,
-    ) -> Result<String, String> {
-        let mut project = self.projects.get(&id).unwrap();
-        if project.project_owner != env::signer_account_id() {
-            Err("You can only edit projects you own.".to_string())
-        } else {
-            match github_issue_link {
-                None => (),
-                Some(link) => {
-                    project.github_issue_link = link;
-                }
+        apply_by: Option<U64>,
+        deliver_by: Option<U64>,
+    ) -> Result<String, ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        match github_issue_link {
+            None => (),
+            Some(link) => {
+                project.github_issue_link = link;
             }
-            match description {
-                None => (),
-                Some(desc) => {
-                    project.description = desc;
-                }
+        }
+        match description {
+            None => (),
+            Some(desc) => {
+                project.description = desc;
             }
-            match reward {
-                None => (),
-                Some(reward_string) => {
-                    project.reward = reward_string.parse().unwrap();
+        }
+        if apply_by.is_some() {
+            project.apply_by = apply_by;
+        }
+        if deliver_by.is_some() {
+            project.deliver_by = deliver_by;
+        }
+        self.save_project(&mut project);
+        self.record(&project, Some(project.status), ProjectEvent::updated(&project));
+        Ok(id)
+    }
+
+    #[payable]
+    pub fn increase_reward(&mut self, id: String) -> Result<u128, ProjectError> {
+        fail_on_error(self.top_up_reward(id))
+    }
+
+    pub fn decrease_reward(&mut self, id: String, amount: String) -> Result<Promise, ProjectError> {
+        let mut project = self.get_project(&id)?;
+        let amount = parse_amount(&amount)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        if project.status != Status::Created {
+            Err(ProjectError::WorkerAlreadyAssigned)
+        } else if !project.milestones.is_empty() {
+            Err(ProjectError::MilestonesDefined)
+        } else if amount == 0 {
+            Err(ProjectError::ZeroAmount)
+        } else if amount > project.reward {
+            Err(ProjectError::AmountExceedsReward)
+        } else {
+            project.reward -= amount;
+            self.save_project(&mut project);
+            self.record_refund(&project, amount);
+            self.record(&project, Some(project.status), ProjectEvent::reward_changed(&project));
+            Ok(self
+                .transfer_reward(&project, project.project_owner.clone(), amount)
+                .then(ext_self::resolve_reward_refund(
+                    id,
+                    amount,
+                    &env::current_account_id(),
+                    0,
+                    GAS_FOR_RESOLVE_TRANSFER,
+                )))
+        }
+    }
+
+    // Replaces the milestones of a project. Passing an empty list turns milestones off again. They
+    // are part of the terms the worker takes the job on, so they are fixed once one is assigned.
+    pub fn set_milestones(
+        &mut self,
+        id: String,
+        milestones: Vec<MilestoneInput>,
+    ) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        if project.status != Status::Created
+            || project
+                .milestones
+                .iter()
+                .any(|milestone| milestone.status != MilestoneStatus::Pending)
+        {
+            return Err(ProjectError::MilestonesLocked);
+        }
+        let mut new_milestones = Vec::new();
+        let mut total: u128 = 0;
+        for milestone in milestones {
+            let amount = parse_amount(&milestone.amount)?;
+            if amount == 0 {
+                return Err(ProjectError::ZeroAmount);
+            }
+            total = total
+                .checked_add(amount)
+                .ok_or_else(|| ProjectError::InvalidAmount {
+                    amount: milestone.amount.clone(),
+                })?;
+            new_milestones.push(Milestone::new(milestone.title, amount));
+        }
+        if !new_milestones.is_empty() && total != project.reward {
+            return Err(ProjectError::MilestoneTotalMismatch {
+                total,
+                reward: project.reward,
+            });
+        }
+        project.milestones = new_milestones;
+        self.save_project(&mut project);
+        self.record(
+            &project,
+            Some(project.status),
+            ProjectEvent::MilestonesSet {
+                project_id: id,
+                milestones: project.milestones.len() as u64,
+            },
+        );
+        Ok(())
+    }
+
+    // Called by the worker when the next milestone is ready for review
+    pub fn submit_milestone(&mut self, id: String, index: u64) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::Worker)?;
+        if project.status != Status::NotStarted && project.status != Status::InProgress {
+            return Err(ProjectError::NotBeingWorkedOn);
+        }
+        let next = project
+            .milestones
+            .iter()
+            .position(|milestone| milestone.status != MilestoneStatus::Approved);
+        match next {
+            Some(next) if next == index as usize => {
+                let milestone = &mut project.milestones[next];
+                if milestone.status == MilestoneStatus::Submitted {
+                    return Err(ProjectError::MilestoneAlreadySubmitted);
                 }
+                milestone.status = MilestoneStatus::Submitted;
             }
-            self.projects.remove(&id);
-            self.projects.insert(&id, &project);
-            Ok(id)
+            Some(next) => return Err(ProjectError::MilestoneOutOfOrder { next: next as u64 }),
+            None => return Err(ProjectError::NoMilestonesLeft),
+        }
+        let previous_status = project.status;
+        if project.status == Status::NotStarted {
+            project.transition(Status::InProgress)?;
         }
+        self.save_project(&mut project);
+        self.record(
+            &project,
+            Some(previous_status),
+            ProjectEvent::WorkSubmitted {
+                project_id: id,
+                worker_id: env::predecessor_account_id(),
+                milestone: Some(index),
+            },
+        );
+        Ok(())
     }
 
-    pub fn remove_project(&mut self, job_id: String) -> Result<(), String> {
-        let project = self.projects.get(&job_id).unwrap();
-        match project.status {
-            Status::Complete => {
-                Err("The job is complete and therefore cannot be removed.".to_string())
+    // Approves a submitted milestone and releases its amount to the worker. The job is marked
+    // complete once the final milestone is approved.
+    pub fn approve_milestone(&mut self, id: String, index: u64) -> Result<Promise, ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        let amount = match project.milestones.get_mut(index as usize) {
+            Some(milestone) if milestone.status == MilestoneStatus::Submitted => {
+                milestone.status = MilestoneStatus::Approved;
+                milestone.amount
             }
-            Status::InProgress => {
-                Err("The job is in progress. You must first remove the user from job.".to_string())
+            Some(_) => return Err(ProjectError::MilestoneNotSubmitted),
+            None => return Err(ProjectError::MilestoneNotFound { index }),
+        };
+        let previous_status = project.status;
+        if project
+            .milestones
+            .iter()
+            .all(|milestone| milestone.status == MilestoneStatus::Approved)
+        {
+            project.transition(Status::Complete)?;
+        }
+        let worker_id = project.worker.clone().ok_or(ProjectError::NoWorkerAssigned)?;
+        self.save_project(&mut project);
+        self.record_payout(&project, amount);
+        if project.status == Status::Complete {
+            self.record(
+                &project,
+                Some(previous_status),
+                ProjectEvent::ProjectCompleted {
+                    project_id: id,
+                    worker_id: worker_id.clone(),
+                },
+            );
+        } else {
+            self.record(
+                &project,
+                Some(previous_status),
+                ProjectEvent::MilestoneApproved {
+                    project_id: id,
+                    worker_id: worker_id.clone(),
+                    milestone: index,
+                },
+            );
+        }
+        Ok(self.pay_worker(&project, worker_id, amount))
+    }
+
+    pub fn remove_project(&mut self, job_id: String) -> Result<Promise, ProjectError> {
+        let project = self.get_project(&job_id)?;
+        if project.status != Status::Created {
+            // A job with a worker has to be freed first. Finished jobs are kept for the record.
+            return Err(ProjectError::NotRemovable {
+                status: project.status,
+            });
+        }
+        self.authorize(&project, Role::ProjectOwner)?;
+        self.projects.remove(&job_id);
+        self.legacy_projects.remove(&job_id);
+        self.project_ids.remove(&job_id);
+        self.pending_removals.insert(&job_id);
+        self.remove_owner_project(&project.project_owner, &job_id);
+        self.applications.remove(&job_id);
+        self.bids.remove(&job_id);
+        // A refund that bounced earlier goes back together with the reward
+        let amount = project.reward + project.unrefunded_amount;
+        self.record_refund(&project, amount);
+        self.record(
+            &project,
+            Some(project.status),
+            ProjectEvent::ProjectRemoved {
+                project_id: job_id,
+                owner_id: project.project_owner.clone(),
+            },
+        );
+        // Send the escrowed reward back to the company. If the transfer fails the
+        // callback puts the project back so the funds are not lost.
+        Ok(self
+            .transfer_reward(&project, project.project_owner.clone(), amount)
+            .then(ext_self::resolve_project_refund(
+                project,
+                &env::current_account_id(),
+                0,
+                GAS_FOR_RESOLVE_TRANSFER,
+            )))
+    }
+
+    #[private]
+    pub fn resolve_project_refund(&mut self, project: Project) -> bool {
+        self.pending_removals.remove(&project.id);
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => true,
+            _ => {
+                let mut project = project;
+                let amount = project.reward + project.unrefunded_amount;
+                self.revert_refund(&project, amount);
+                self.project_ids.insert(&project.id);
+                self.add_owner_project(&project.project_owner, &project.id);
+                self.save_project(&mut project);
+                self.record(
+                    &project,
+                    Some(project.status),
+                    ProjectEvent::refund_failed(&project, amount),
+                );
+                false
             }
-            Status::Created => {
-                // check if the company requested it
-                if project.project_owner == env::signer_account_id() {
-                    self.projects.remove(&job_id).unwrap();
-                    self.project_ids.remove(&job_id);
-                    Ok(())
-                } else {
-                    Err("The company must request this.".to_string())
+        }
+    }
+
+    #[private]
+    pub fn resolve_reward_refund(&mut self, id: String, amount: u128) -> bool {
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => true,
+            _ => {
+                // The refund bounced, so the funds are still held by the contract.
+                if let Some(mut project) = self.find_project(&id) {
+                    project.reward += amount;
+                    self.save_project(&mut project);
+                    self.revert_refund(&project, amount);
+                    self.record(
+                        &project,
+                        Some(project.status),
+                        ProjectEvent::refund_failed(&project, amount),
+                    );
                 }
+                false
             }
-            Status::PendingFinalApproval => {
-                Err("The job is pending final approval and therefore cannot be removed.".to_string())
+        }
+    }
+
+    // Callback of the refunds that must not go back into the reward, such as the company's share
+    // of a settled dispute or what the reward exceeded an accepted bid by. A bounced refund stays
+    // owed to the company.
+    #[private]
+    pub fn resolve_owner_refund(&mut self, id: String, amount: u128) -> bool {
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => true,
+            _ => {
+                if let Some(mut project) = self.find_project(&id) {
+                    project.unrefunded_amount += amount;
+                    self.save_project(&mut project);
+                    self.revert_refund(&project, amount);
+                    self.record(
+                        &project,
+                        Some(project.status),
+                        ProjectEvent::refund_failed(&project, amount),
+                    );
+                }
+                false
             }
-            Status::NotStarted => {
-                Err("This job has a user assigned and cannot be removed. You must first remove the user from job.".to_string())
+        }
+    }
+
+    #[private]
+    pub fn resolve_payout(&mut self, id: String, receiver_id: AccountId, amount: u128) -> bool {
+        let mut project = match self.find_project(&id) {
+            Some(project) => project,
+            None => return false,
+        };
+        let previous_status = project.status;
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                project.paid_amount += amount;
+                self.save_project(&mut project);
+                self.record(
+                    &project,
+                    Some(previous_status),
+                    ProjectEvent::PayoutSent {
+                        project_id: id,
+                        receiver_id,
+                        amount: U128(amount),
+                    },
+                );
+                true
             }
-            Status::PendingWorkerApproval => {
-                Err("This job is has a worked awaiting approval and cannot be removed. You must decline the user the job".to_string())
+            _ => {
+                // The transfer bounced, so the contract still holds the funds for the worker. The
+                // status is left alone when the job has moved on to a state that cannot fail.
+                project.unpaid_amount += amount;
+                let _ = project.transition(Status::PayoutFailed);
+                self.save_project(&mut project);
+                self.revert_payout(&project, amount);
+                self.record(
+                    &project,
+                    Some(previous_status),
+                    ProjectEvent::PayoutFailed {
+                        project_id: id,
+                        receiver_id,
+                        amount: U128(amount),
+                    },
+                );
+                false
             }
         }
     }
 
-    pub fn set_project_status(&mut self, id: String, status: String) -> Result<(), String> {
-        let mut project = self.projects.get(&id).unwrap();
-        if !project.worker.is_some() {
-            Err("Only jobs with workers assigned can be changed".to_string())
+    // Sends a failed payout to the worker again. Either the company or the worker may call it.
+    pub fn retry_payout(&mut self, id: String) -> Result<Promise, ProjectError> {
+        let project = self.get_project(&id)?;
+        self.authorize(&project, Role::OwnerOrWorker)?;
+        let worker_id = project.worker.clone().ok_or(ProjectError::NoWorkerAssigned)?;
+        self.resend_payout(project, worker_id)
+    }
+
+    // Sends a failed payout to another account. Only the worker can choose where the payout goes;
+    // the company can resend it to the worker with retry_payout.
+    pub fn claim_payout(&mut self, id: String, receiver_id: AccountId) -> Result<Promise, ProjectError> {
+        let project = self.get_project(&id)?;
+        self.authorize(&project, Role::Worker)?;
+        self.resend_payout(project, receiver_id)
+    }
+
+    // Sends a refund that bounced back to the company again
+    pub fn claim_refund(&mut self, id: String) -> Result<Promise, ProjectError> {
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        if project.unrefunded_amount == 0 {
+            return Err(ProjectError::NoFailedRefund);
+        }
+        let amount = project.unrefunded_amount;
+        project.unrefunded_amount = 0;
+        self.save_project(&mut project);
+        self.record_refund(&project, amount);
+        self.record(
+            &project,
+            Some(project.status),
+            ProjectEvent::RefundClaimed {
+                project_id: id,
+                owner_id: project.project_owner.clone(),
+                amount: U128(amount),
+            },
+        );
+        Ok(self.refund_owner(&project, amount))
+    }
+
+    pub fn set_project_status(&mut self, id: String, status: String) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        if project.worker.is_none() {
+            Err(ProjectError::NoWorkerAssigned)
+        } else if let Err(e) = self.authorize(&project, Role::Worker) {
+            Err(e)
         } else if project.status == Status::PendingWorkerApproval {
-            Err("This job is pending a worker approval and cannot be changed".to_string())
+            Err(ProjectError::AwaitingWorkerApproval)
+        } else if project.status == Status::Disputed {
+            Err(ProjectError::ProjectDisputed)
         } else {
+            let previous_status = project.status;
             match status.to_lowercase().as_str() {
-                "not started" => project.status = Status::NotStarted,
-                "in progress" => project.status = Status::InProgress,
-                "pending final approval" => project.status = Status::InProgress,
-                _ => return Err(format!("{} is not a valid value", &status).to_string()),
+                "not started" => project.transition(Status::NotStarted)?,
+                "in progress" => project.transition(Status::InProgress)?,
+                "pending final approval" => {
+                    return Err(ProjectError::UseSubmitWork)
+                }
+                _ => return Err(ProjectError::InvalidStatus { status }),
             }
-            self.projects.remove(&id).unwrap();
-            self.projects.insert(&id, &project);
+            self.save_project(&mut project);
+            self.record(
+                &project,
+                Some(previous_status),
+                ProjectEvent::StatusChanged {
+                    project_id: id,
+                    status: project.status,
+                },
+            );
             Ok(())
         }
     }
 
-    pub fn set_user_for_project(&mut self, id: String, worker_id: String) -> Result<(), String> {
-        let mut project = self.projects.get(&id).unwrap();
-        if project.worker.is_some() {
-            Err("The project done has a user attached to it. Please remove the user before preceding.".to_string())
+    pub fn set_user_for_project(&mut self, id: String, worker_id: String) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        // Volunteering follows the same rules as applying, except that there is no pitch
+        if worker_id != env::predecessor_account_id() {
+            Err(ProjectError::SelfAssignmentOnly)
+        } else if project.worker.is_some() {
+            Err(ProjectError::WorkerAlreadyAssigned)
+        } else if project.bidding {
+            Err(ProjectError::BiddingOnly)
+        } else if !project.is_accepting_applications() {
+            Err(ProjectError::ApplicationDeadlinePassed)
+        } else if project.project_owner == worker_id {
+            Err(ProjectError::OwnProject)
         } else {
+            let previous_status = project.status;
+            project.transition(Status::PendingWorkerApproval)?;
             project.worker = Option::from(worker_id.clone());
-            project.status = Status::PendingWorkerApproval;
-            self.projects.remove(&id);
-            self.projects.insert(&id, &project);
-            let user_projects_result = self.user_projects.get(&worker_id);
-            let user_projects = match user_projects_result {
-                None => {
-                    let mut set: LookupMap<String, Project> = LookupMap::new(b"p");
-                    set.insert(&id, &self.projects.get(&id).unwrap());
-                    set
-                }
-                Some(mut set) => {
-                    set.insert(&id, &self.projects.get(&id).unwrap());
-                    set
-                }
-            };
-            self.user_ids.insert(&id);
-            self.user_projects.insert(&worker_id, &user_projects);
+            self.save_project(&mut project);
+            self.add_user_project(&worker_id, &id);
+            self.record(
+                &project,
+                Some(previous_status),
+                ProjectEvent::WorkerAssigned {
+                    project_id: id,
+                    worker_id,
+                    status: project.status,
+                },
+            );
             Ok(())
         }
     }
 
-    pub fn approve_user_for_project(&mut self, id: String, approve: bool) -> Result<(), String> {
-        let mut project = self.projects.get(&id).unwrap();
-        if project.project_owner != env::signer_account_id() {
-            Err("Only the company can approve a user.".to_string())
+    pub fn apply_to_project(
+        &mut self,
+        id: String,
+        pitch: String,
+        estimated_completion: Option<String>,
+    ) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let project = self.get_project(&id)?;
+        let worker_id = env::predecessor_account_id();
+        let mut applications = self.applications.get(&id).unwrap_or_default();
+        if project.status != Status::Created {
+            Err(ProjectError::NotAcceptingApplications)
+        } else if !project.is_accepting_applications() {
+            Err(ProjectError::ApplicationDeadlinePassed)
+        } else if project.bidding {
+            Err(ProjectError::BiddingOnly)
+        } else if project.project_owner == worker_id {
+            Err(ProjectError::OwnProject)
+        } else if applications.iter().any(|application| {
+            application.worker_id == worker_id && application.status == ApplicationStatus::Pending
+        }) {
+            Err(ProjectError::AlreadyApplied)
+        } else {
+            applications.push(Application::new(worker_id, pitch, estimated_completion));
+            self.applications.insert(&id, &applications);
+            Ok(())
+        }
+    }
+
+    // Accepts one application, assigns its worker to the job and declines every other application
+    pub fn accept_application(&mut self, id: String, worker_id: String) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        let mut applications = self.applications.get(&id).unwrap_or_default();
+        self.authorize(&project, Role::ProjectOwner)?;
+        if project.worker.is_some() {
+            return Err(ProjectError::WorkerAlreadyAssigned);
+        }
+        let previous_status = project.status;
+        project.transition(Status::NotStarted)?;
+        if !applications.iter().any(|application| {
+            application.worker_id == worker_id && application.status == ApplicationStatus::Pending
+        }) {
+            return Err(ProjectError::ApplicationNotFound { worker_id });
+        }
+        for application in applications.iter_mut() {
+            if application.status == ApplicationStatus::Pending {
+                application.status = if application.worker_id == worker_id {
+                    ApplicationStatus::Accepted
+                } else {
+                    ApplicationStatus::Declined
+                };
+            }
+        }
+        self.applications.insert(&id, &applications);
+        // The worker asked for the job, so there is no need to wait for them to approve it
+        project.worker = Some(worker_id.clone());
+        self.save_project(&mut project);
+        self.add_user_project(&worker_id, &id);
+        self.record(
+            &project,
+            Some(previous_status),
+            ProjectEvent::WorkerAssigned {
+                project_id: id,
+                worker_id,
+                status: project.status,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn set_bidding(&mut self, id: String, bidding: bool) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        if project.status != Status::Created {
+            Err(ProjectError::WorkerAlreadyAssigned)
+        } else {
+            project.bidding = bidding;
+            self.save_project(&mut project);
+            self.record(
+                &project,
+                Some(project.status),
+                ProjectEvent::BiddingChanged {
+                    project_id: id,
+                    bidding,
+                },
+            );
+            Ok(())
+        }
+    }
+
+    pub fn submit_bid(
+        &mut self,
+        id: String,
+        amount: String,
+        timeline: String,
+    ) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let project = self.get_project(&id)?;
+        let amount = parse_amount(&amount)?;
+        let worker_id = env::predecessor_account_id();
+        let mut bids = self.bids.get(&id).unwrap_or_default();
+        if !project.bidding {
+            Err(ProjectError::NotBidding)
+        } else if project.status != Status::Created || !project.is_accepting_applications() {
+            Err(ProjectError::NotAcceptingBids)
+        } else if project.project_owner == worker_id {
+            Err(ProjectError::OwnProject)
+        } else if amount == 0 {
+            Err(ProjectError::ZeroAmount)
+        } else if bids
+            .iter()
+            .any(|bid| bid.worker_id == worker_id && bid.status == BidStatus::Pending)
+        {
+            Err(ProjectError::AlreadyBid)
         } else {
+            bids.push(Bid::new(worker_id, amount, timeline));
+            self.bids.insert(&id, &bids);
+            Ok(())
+        }
+    }
+
+    // Accepts a bid. A bid below the reward refunds the difference to the company. A bid above it
+    // only takes effect once the reward has been topped up, either with the deposit attached here
+    // or later through increase_reward.
+    #[payable]
+    pub fn accept_bid(
+        &mut self,
+        id: String,
+        worker_id: String,
+    ) -> Result<Option<Promise>, ProjectError> {
+        fail_on_error(self.choose_bid(id, worker_id))
+    }
+
+    // Lets the company take back a job whose worker missed the deliver_by deadline. The worker is
+    // removed and the deadline is cleared so a new one can be set with update_project.
+    pub fn reclaim_project(&mut self, id: String) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        if !project.is_overdue() {
+            Err(ProjectError::NotOverdue)
+        } else {
+            let previous_status = project.status;
+            project.transition(Status::Created)?;
+            let worker_id = project.worker.take();
+            if let Some(worker_id) = &worker_id {
+                self.remove_user_project(worker_id, &id);
+            }
+            project.deliver_by = None;
+            self.save_project(&mut project);
+            self.record(
+                &project,
+                Some(previous_status),
+                ProjectEvent::ProjectReclaimed {
+                    project_id: id,
+                    worker_id,
+                },
+            );
+            Ok(())
+        }
+    }
+
+    pub fn approve_user_for_project(&mut self, id: String, approve: bool) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        if let Err(e) = self.authorize(&project, Role::ProjectOwner) {
+            Err(e)
+        } else if project.worker.is_none() {
+            Err(ProjectError::NoWorkerAssigned)
+        } else {
+            let worker_id = project.worker.clone().unwrap_or_default();
+            let previous_status = project.status;
             match approve {
                 true => {
-                    project.status = Status::NotStarted;
+                    project.transition(Status::NotStarted)?;
+                    self.save_project(&mut project);
+                    self.record(
+                        &project,
+                        Some(previous_status),
+                        ProjectEvent::WorkerApproved {
+                            project_id: id,
+                            worker_id,
+                        },
+                    );
                 }
                 false => {
-                    project.status = Status::Created;
-                    self.user_projects
-                        .get(&project.worker.unwrap())
-                        .unwrap()
-                        .remove(&id);
-                    self.user_ids.remove(&id);
+                    project.transition(Status::Created)?;
                     project.worker = None;
+                    self.remove_user_project(&worker_id, &id);
+                    self.save_project(&mut project);
+                    self.record(
+                        &project,
+                        Some(previous_status),
+                        ProjectEvent::WorkerRejected {
+                            project_id: id,
+                            worker_id,
+                        },
+                    );
                 }
             }
-            self.projects.remove(&id);
-            self.projects.insert(&id, &project);
             Ok(())
         }
     }
@@ -200,105 +972,386 @@ impl ProjectManagement {
         &mut self,
         job_id: String,
         worker_id: String,
-    ) -> Result<(), String> {
-        let mut project = self.projects.get(&job_id).unwrap();
-        match project.status {
-            Status::Complete => Err(
-                "The job is marked as complete and therefore you cannot remove user from job."
-                    .to_string(),
-            ),
-            Status::InProgress | Status::NotStarted | Status::PendingWorkerApproval => {
-                // check if the worker or the the company requested it
-                if project.worker.unwrap() == env::signer_account_id()
-                    || project.project_owner == env::signer_account_id()
-                {
-                    project.worker = None;
-                    project.status = Status::Created;
-                    self.user_projects
-                        .get(&worker_id)
-                        .unwrap()
-                        .remove(&job_id)
-                        .unwrap();
-                    self.user_ids.remove(&job_id);
-                    self.projects.remove(&job_id).unwrap();
-                    self.projects.insert(&job_id, &project).unwrap();
-                    Ok(())
-                } else {
-                    Err("The worker or the company must request it to be removed.".to_string())
-                }
-            }
-            Status::Created => Err("The job has no user assigned to it.".to_string()),
-            Status::PendingFinalApproval => Err("The job is pending final approval.".to_string()),
+    ) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&job_id)?;
+        if project.worker.is_none() {
+            return Err(ProjectError::NoWorkerAssigned);
+        } else if project.worker != Some(worker_id.clone()) {
+            return Err(ProjectError::NotAssignedWorker { worker_id });
+        }
+        // check if the worker or the the company requested it
+        self.authorize(&project, Role::OwnerOrWorker)?;
+        let previous_status = project.status;
+        // Submitted, disputed and finished jobs cannot go back to Created
+        project.transition(Status::Created)?;
+        project.worker = None;
+        self.remove_user_project(&worker_id, &job_id);
+        self.save_project(&mut project);
+        self.record(
+            &project,
+            Some(previous_status),
+            ProjectEvent::WorkerRemoved {
+                project_id: job_id,
+                worker_id,
+            },
+        );
+        Ok(())
+    }
+
+    // Called by the worker to hand in the job for final approval
+    pub fn submit_work(
+        &mut self,
+        id: String,
+        pull_request_url: String,
+        notes: String,
+    ) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::Worker)?;
+        if project.status != Status::NotStarted && project.status != Status::InProgress {
+            Err(ProjectError::NotBeingWorkedOn)
+        } else if !project.milestones.is_empty() {
+            Err(ProjectError::PaidByMilestone)
+        } else {
+            project
+                .submissions
+                .push(Submission::new(pull_request_url, notes));
+            let previous_status = project.status;
+            project.transition(Status::PendingFinalApproval)?;
+            let now = env::block_timestamp();
+            project.submitted_at = Some(U64(now));
+            project.review_by = Some(U64(now.saturating_add(self.review_window)));
+            self.save_project(&mut project);
+            self.record(
+                &project,
+                Some(previous_status),
+                ProjectEvent::WorkSubmitted {
+                    project_id: id,
+                    worker_id: env::predecessor_account_id(),
+                    milestone: None,
+                },
+            );
+            Ok(())
         }
     }
 
+    // Approving pays the worker. Rejecting needs feedback and sends the job back to in progress.
     pub fn approve_submission(
         &mut self,
         id: String,
         approve: bool,
-    ) -> Result<ApproveReturn, String> {
-        let project = self.projects.get(&id).unwrap();
-        if project.project_owner != env::signer_account_id() {
-            Err("Only the company can approve a project".to_string())
+        feedback: Option<String>,
+    ) -> Result<ApproveReturn, ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        if project.status != Status::PendingFinalApproval {
+            Err(ProjectError::NoWorkSubmitted)
         } else {
-            return match approve {
+            match approve {
                 true => {
-                    let result = self.set_project_complete(id.clone());
+                    let result = self.complete_project(project);
                     match result {
                         Ok(promise) => Ok(ApproveReturn::Promise(promise)),
                         Err(e) => Err(e),
                     }
                 }
-                false => Ok(ApproveReturn::String(
-                    "project was returned to in progress state".to_string(),
-                )),
-            };
+                false => {
+                    let feedback = match feedback {
+                        Some(feedback) if !feedback.trim().is_empty() => feedback,
+                        _ => return Err(ProjectError::FeedbackRequired),
+                    };
+                    if let Some(submission) = project.submissions.last_mut() {
+                        submission.feedback = Some(feedback);
+                    }
+                    let previous_status = project.status;
+                    project.transition(Status::InProgress)?;
+                    project.revisions += 1;
+                    self.save_project(&mut project);
+                    self.record(
+                        &project,
+                        Some(previous_status),
+                        ProjectEvent::RevisionRequested {
+                            project_id: id,
+                            worker_id: project.worker.clone().unwrap_or_default(),
+                            revisions: project.revisions,
+                        },
+                    );
+                    Ok(ApproveReturn::String(
+                        "project was returned to in progress state".to_string(),
+                    ))
+                }
+            }
         }
     }
 
-    pub fn set_project_complete(&mut self, id: String) -> Result<Promise, String> {
-        let mut project = self.projects.get(&id).unwrap();
-        assert_ne!(project.status, Status::Complete);
-        let worker_id = project.worker.as_ref().unwrap();
-        project.status = Status::Complete;
-        self.projects.remove(&id);
-        self.projects.insert(&id, &project);
-        self.user_projects
-            .get(&worker_id)
-            .unwrap()
-            .get(&id)
-            .unwrap()
-            .status = Status::Complete;
-        Ok(Promise::new(worker_id.clone()).transfer(self.projects.get(&id).unwrap().reward))
+    // Applies to work handed in from now on
+    pub fn set_review_window(&mut self, review_window: U64) -> Result<(), ProjectError> {
+        self.authorize_admin()?;
+        self.review_window = review_window.0;
+        Ok(())
     }
 
-    pub fn get_all_projects(&self) -> AllProjectsReturn {
-        let mut projects = AllProjectsReturn::new();
+    // Lets the worker collect the reward when the company has not reviewed the submitted work
+    // within the review window
+    pub fn claim_after_timeout(&mut self, id: String) -> Result<Promise, ProjectError> {
+        self.ensure_not_paused()?;
+        let project = self.get_project(&id)?;
+        self.authorize(&project, Role::Worker)?;
+        if project.status != Status::PendingFinalApproval {
+            return Err(ProjectError::NoWorkSubmitted);
+        }
+        // Work handed in to the first contract has no review_by and gets the current window
+        let review_by = project.review_by.map_or_else(
+            || {
+                project
+                    .submitted_at
+                    .map_or(0, |submitted_at| submitted_at.0)
+                    .saturating_add(self.review_window)
+            },
+            |review_by| review_by.0,
+        );
+        if env::block_timestamp() < review_by {
+            return Err(ProjectError::ReviewWindowOpen);
+        }
+        self.complete_project(project)
+    }
+
+    pub fn set_default_arbiter(&mut self, arbiter_id: Option<AccountId>) -> Result<(), ProjectError> {
+        self.authorize_admin()?;
+        self.default_arbiter = arbiter_id;
+        Ok(())
+    }
+
+    // Sets who rules on disputes for this job. It can only be changed before a worker is assigned.
+    pub fn set_project_arbiter(
+        &mut self,
+        id: String,
+        arbiter_id: AccountId,
+    ) -> Result<(), ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        if project.status != Status::Created {
+            Err(ProjectError::WorkerAlreadyAssigned)
+        } else if arbiter_id == project.project_owner {
+            Err(ProjectError::OwnerAsArbiter)
+        } else {
+            project.arbiter = Some(arbiter_id.clone());
+            self.save_project(&mut project);
+            self.record(
+                &project,
+                Some(project.status),
+                ProjectEvent::ArbiterSet {
+                    project_id: id,
+                    arbiter_id,
+                },
+            );
+            Ok(())
+        }
+    }
+
+    // Either the company or the worker can hand a job in progress or awaiting approval to the arbiter
+    pub fn open_dispute(&mut self, id: String, reason: String) -> Result<AccountId, ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::OwnerOrWorker)?;
+        let arbiter = project.arbiter.clone().or_else(|| self.default_arbiter.clone());
+        // Only jobs in progress or pending final approval can be disputed
+        let previous_status = project.status;
+        project.transition(Status::Disputed)?;
+        let arbiter = match arbiter {
+            Some(arbiter)
+                if arbiter != project.project_owner && project.worker != Some(arbiter.clone()) =>
+            {
+                arbiter
+            }
+            _ => return Err(ProjectError::NoArbiter),
+        };
+        project.dispute = Some(Dispute::new(
+            env::predecessor_account_id(),
+            reason,
+            arbiter.clone(),
+            previous_status,
+        ));
+        self.save_project(&mut project);
+        self.record(
+            &project,
+            Some(previous_status),
+            ProjectEvent::DisputeOpened {
+                project_id: id,
+                opened_by: env::predecessor_account_id(),
+                arbiter_id: arbiter.clone(),
+            },
+        );
+        Ok(arbiter)
+    }
+
+    // Called by the arbiter to settle a dispute. The worker receives `worker_percentage` of the
+    // reward that has not been paid out yet and the company gets the rest back, so 100 pays the
+    // worker in full and 0 refunds the company.
+    pub fn resolve_dispute(
+        &mut self,
+        id: String,
+        worker_percentage: u8,
+    ) -> Result<Option<Promise>, ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        if project.status != Status::Disputed {
+            return Err(ProjectError::NotDisputed);
+        }
+        self.authorize(&project, Role::Arbiter)?;
+        let mut dispute = project.dispute.take().ok_or(ProjectError::NotDisputed)?;
+        if worker_percentage > 100 {
+            return Err(ProjectError::InvalidPercentage);
+        }
+        let already_paid: u128 = project
+            .milestones
+            .iter()
+            .filter(|milestone| milestone.status == MilestoneStatus::Approved)
+            .map(|milestone| milestone.amount)
+            .sum();
+        let remaining = project.reward - already_paid;
+        // Split before multiplying so a large token reward cannot overflow
+        let percentage = worker_percentage as u128;
+        let worker_amount = remaining / 100 * percentage + remaining % 100 * percentage / 100;
+        let owner_amount = remaining - worker_amount;
+        let previous_status = project.status;
+        project.transition(Status::Complete)?;
+        dispute.worker_percentage = Some(worker_percentage);
+        project.dispute = Some(dispute);
+        self.save_project(&mut project);
+        self.record(
+            &project,
+            Some(previous_status),
+            ProjectEvent::ProjectCompleted {
+                project_id: id.clone(),
+                worker_id: project.worker.clone().unwrap_or_default(),
+            },
+        );
+
+        let mut promise = None;
+        if worker_amount > 0 {
+            self.record_payout(&project, worker_amount);
+            let worker_id = project.worker.clone().ok_or(ProjectError::NoWorkerAssigned)?;
+            promise = Some(self.pay_worker(&project, worker_id, worker_amount));
+        }
+        if owner_amount > 0 {
+            self.record_refund(&project, owner_amount);
+            let refund = self.refund_owner(&project, owner_amount);
+            promise = Some(match promise {
+                Some(payout) => payout.and(refund),
+                None => refund,
+            });
+        }
+        Ok(promise)
+    }
+
+    pub fn set_project_complete(&mut self, id: String) -> Result<Promise, ProjectError> {
+        self.ensure_not_paused()?;
+        let project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        self.complete_project(project)
+    }
+
+    // Rebuilds user_projects from `projects`, for when the index no longer matches the jobs. Every
+    // worker's set is cleared first so no stale ids are left behind.
+    #[private]
+    pub fn migrate_user_projects(&mut self) {
+        for worker_id in self.user_projects.keys_as_vector().to_vec() {
+            if let Some(mut user_projects) = self.user_projects.remove(&worker_id) {
+                user_projects.clear();
+            }
+        }
         for id in self.project_ids.to_vec() {
-            let project = self.projects.get(&id).unwrap();
-            match project.status {
-                Status::Complete => projects.complete.push(project),
-                Status::InProgress => {
-                    projects.in_progress.push(project);
-                }
-                Status::Created => {
-                    projects.created.push(project);
-                }
-                Status::PendingWorkerApproval => {
-                    projects.pending_work_approval.push(project);
+            if let Some(worker_id) = self.find_project(&id).and_then(|project| project.worker) {
+                self.add_user_project(&worker_id, &id);
+            }
+        }
+    }
+
+    // Pages through every project. Without `sort` each call reads at most `limit` ids, so a page
+    // can come back with fewer projects than the limit when the filter skips some of them. With
+    // `sort` every project is read, filtered and ordered first, and the cursor then pages through
+    // the sorted matches, so the cost grows with the size of the board.
+    pub fn list_projects(
+        &self,
+        from_index: Option<U64>,
+        limit: Option<U64>,
+        filter: Option<ProjectFilter>,
+        sort: Option<ProjectSort>,
+    ) -> ProjectPage {
+        let filter = filter.unwrap_or_default();
+        let sort = match sort {
+            Some(sort) => sort,
+            None => {
+                let (ids, next_index) = page_ids(&self.project_ids, from_index, limit);
+                let projects = ids
+                    .iter()
+                    .filter_map(|id| self.find_project(id))
+                    .filter(|project| filter.matches(project))
+                    .collect();
+                return ProjectPage::new(projects, next_index);
+            }
+        };
+        let mut projects: Vec<Project> = self
+            .project_ids
+            .iter()
+            .filter_map(|id| self.find_project(&id))
+            .filter(|project| filter.matches(project))
+            .collect();
+        sort.sort(&mut projects);
+        let (start, end, next_index) = page_range(projects.len() as u64, from_index, limit);
+        ProjectPage::new(projects.drain(start as usize..end as usize).collect(), next_index)
+    }
+
+    pub fn get_all_projects(&self, from_index: Option<U64>, limit: Option<U64>) -> AllProjectsReturn {
+        let mut projects = AllProjectsReturn::new();
+        let (ids, next_index) = page_ids(&self.project_ids, from_index, limit);
+        for id in ids {
+            if let Some(project) = self.find_project(&id) {
+                projects.push(project);
+            }
+        }
+        projects.next_index = next_index;
+        projects
+    }
+
+    pub fn get_owner_projects(
+        &self,
+        owner_id: AccountId,
+        from_index: Option<U64>,
+        limit: Option<U64>,
+    ) -> AllProjectsReturn {
+        let mut projects = AllProjectsReturn::new();
+        if let Some(owner_projects) = self.owner_projects.get(&owner_id) {
+            let (ids, next_index) = page_ids(&owner_projects, from_index, limit);
+            for id in ids {
+                if let Some(project) = self.find_project(&id) {
+                    projects.push(project);
                 }
-                Status::NotStarted => projects.not_started.push(project),
-                Status::PendingFinalApproval => projects.pending_final_approval.push(project),
             }
+            projects.next_index = next_index;
         }
         projects
     }
 
-    pub fn get_user_projects(&self, worker_id: String) -> UserProjectsReturn {
+    pub fn get_user_projects(
+        &self,
+        worker_id: String,
+        from_index: Option<U64>,
+        limit: Option<U64>,
+    ) -> UserProjectsReturn {
         let mut projects = UserProjectsReturn::new();
-        for id in self.user_ids.to_vec() {
-            let result = self.user_projects.get(&worker_id).unwrap().get(&id);
-            match result {
+        let user_projects = match self.user_projects.get(&worker_id) {
+            Some(user_projects) => user_projects,
+            None => return projects,
+        };
+        let (ids, next_index) = page_ids(&user_projects, from_index, limit);
+        projects.next_index = next_index;
+        for id in ids {
+            match self.find_project(&id) {
                 None => {}
                 Some(project) => match project.status {
                     Status::Complete => {
@@ -312,10 +1365,534 @@ impl ProjectManagement {
                     }
                     Status::PendingWorkerApproval => projects.pending_work_approval.push(project),
                     Status::PendingFinalApproval => projects.pending_final_approval.push(project),
+                    Status::PayoutFailed => projects.payout_failed.push(project),
+                    Status::Disputed => projects.disputed.push(project),
                     _ => {}
                 },
             }
         }
         projects
     }
+
+    // The statuses the project can move to next, so frontends know which actions to offer
+    pub fn get_allowed_transitions(&self, id: String) -> Result<Vec<Status>, ProjectError> {
+        Ok(allowed_transitions(self.get_project(&id)?.status))
+    }
+
+    // Every submission of the job together with the feedback the company gave on it
+    pub fn get_submission_thread(&self, id: String) -> Result<Vec<Submission>, ProjectError> {
+        Ok(self.get_project(&id)?.submissions)
+    }
+
+    pub fn get_project_bids(&self, id: String) -> Vec<Bid> {
+        self.bids.get(&id).unwrap_or_default()
+    }
+
+    pub fn get_project_applications(&self, id: String) -> Vec<Application> {
+        self.applications.get(&id).unwrap_or_default()
+    }
+
+    // The project's timeline, oldest first. It is kept after the project is removed, until the id
+    // is used for a new project.
+    pub fn get_project_history(
+        &self,
+        id: String,
+        from_index: Option<U64>,
+        limit: Option<U64>,
+    ) -> Vec<HistoryEntry> {
+        let history = match self.history.get(&id) {
+            Some(history) => history,
+            None => return Vec::new(),
+        };
+        let from_index = from_index
+            .map_or(0, |from_index| from_index.0)
+            .min(history.len());
+        let limit = limit
+            .map_or(DEFAULT_PAGE_LIMIT, |limit| limit.0)
+            .clamp(1, MAX_PAGE_LIMIT);
+        (from_index..from_index.saturating_add(limit).min(history.len()))
+            .filter_map(|index| history.get(index))
+            .collect()
+    }
+
+    pub fn get_escrow_summary(&self) -> EscrowSummary {
+        let mut total_paid_out = 0;
+        let mut total_refunded = 0;
+        let owners = self.escrow_ledger.values_as_vector().to_vec();
+        for escrow in owners.iter() {
+            total_paid_out += escrow.paid_out.0;
+            total_refunded += escrow.refunded.0;
+        }
+        EscrowSummary {
+            total_escrowed: U128(self.total_escrowed),
+            total_paid_out: U128(total_paid_out),
+            total_refunded: U128(total_refunded),
+            contract_balance: U128(env::account_balance()),
+            solvent: env::account_balance() >= self.total_escrowed,
+            owners,
+        }
+    }
+}
+
+impl ProjectManagement {
+    // Marks the bid as the chosen one and assigns its worker once the reward covers it
+    fn choose_bid(
+        &mut self,
+        id: String,
+        worker_id: String,
+    ) -> Result<Option<Promise>, ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        let mut bids = self.bids.get(&id).unwrap_or_default();
+        self.authorize(&project, Role::ProjectOwner)?;
+        if project.status != Status::Created {
+            return Err(ProjectError::NotAcceptingBids);
+        }
+        let amount = match bids.iter().find(|bid| {
+            bid.worker_id == worker_id
+                && (bid.status == BidStatus::Pending || bid.status == BidStatus::AwaitingTopUp)
+        }) {
+            Some(bid) => bid.amount,
+            None => return Err(ProjectError::BidNotFound { worker_id }),
+        };
+        if !project.milestones.is_empty() && amount != project.reward {
+            return Err(ProjectError::MilestonesDefined);
+        }
+        if project.token_id.is_some() && (amount > project.reward || env::attached_deposit() > 0) {
+            return Err(ProjectError::TokenReward);
+        }
+        for bid in bids.iter_mut() {
+            if bid.status == BidStatus::AwaitingTopUp {
+                bid.status = BidStatus::Pending;
+            }
+            if bid.worker_id == worker_id && bid.status == BidStatus::Pending {
+                bid.status = BidStatus::AwaitingTopUp;
+            }
+        }
+        self.bids.insert(&id, &bids);
+        if env::attached_deposit() > 0 {
+            project.reward += env::attached_deposit();
+            self.save_project(&mut project);
+            self.record_deposit(&project, env::attached_deposit());
+            self.record(&project, Some(project.status), ProjectEvent::reward_changed(&project));
+        }
+        Ok(self.complete_awaiting_bid(project))
+    }
+
+    // Adds the attached deposit to the reward
+    fn top_up_reward(&mut self, id: String) -> Result<u128, ProjectError> {
+        self.ensure_not_paused()?;
+        let mut project = self.get_project(&id)?;
+        self.authorize(&project, Role::ProjectOwner)?;
+        // Once the reward has been released to the worker nothing would ever pay out a top up
+        if project.status == Status::Complete || project.status == Status::PayoutFailed {
+            Err(ProjectError::RewardLocked)
+        } else if project.token_id.is_some() {
+            Err(ProjectError::TokenReward)
+        } else if !project.milestones.is_empty() {
+            Err(ProjectError::MilestonesDefined)
+        } else if env::attached_deposit() == 0 {
+            Err(ProjectError::DepositRequired)
+        } else {
+            project.reward += env::attached_deposit();
+            self.save_project(&mut project);
+            self.record_deposit(&project, env::attached_deposit());
+            self.record(&project, Some(project.status), ProjectEvent::reward_changed(&project));
+            let reward = project.reward;
+            // A bid waiting on this top up takes effect now. Any surplus refund runs on its own.
+            self.complete_awaiting_bid(project);
+            Ok(reward)
+        }
+    }
+
+    // Pays `amount` of the project's reward currency to `receiver_id`
+    pub(crate) fn transfer_reward(
+        &self,
+        project: &Project,
+        receiver_id: AccountId,
+        amount: u128,
+    ) -> Promise {
+        match &project.token_id {
+            None => Promise::new(receiver_id).transfer(amount),
+            Some(token_id) => ext_ft::ft_transfer(
+                receiver_id,
+                U128(amount),
+                None,
+                token_id,
+                ONE_YOCTO,
+                GAS_FOR_FT_TRANSFER,
+            ),
+        }
+    }
+
+    // Marks the project complete and pays the worker the full reward
+    fn complete_project(&mut self, mut project: Project) -> Result<Promise, ProjectError> {
+        let id = project.id.clone();
+        if !project.milestones.is_empty() {
+            return Err(ProjectError::PaidByMilestone);
+        }
+        let previous_status = project.status;
+        project.transition(Status::Complete)?;
+        let worker_id = project.worker.clone().ok_or(ProjectError::NoWorkerAssigned)?;
+        self.save_project(&mut project);
+        self.record_payout(&project, project.reward);
+        self.record(
+            &project,
+            Some(previous_status),
+            ProjectEvent::ProjectCompleted {
+                project_id: id,
+                worker_id: worker_id.clone(),
+            },
+        );
+        Ok(self.pay_worker(&project, worker_id, project.reward))
+    }
+
+    // Logs the event and adds it to the history of the project. `previous_status` is the status
+    // the project had before the change, or None when the change created it.
+    fn record(&mut self, project: &Project, previous_status: Option<Status>, event: ProjectEvent) {
+        let mut history = self
+            .history
+            .get(&project.id)
+            .unwrap_or_else(|| Vector::new(history_prefix(&project.id)));
+        history.push(&HistoryEntry::new(
+            event.name().to_string(),
+            env::predecessor_account_id(),
+            previous_status,
+            project.status,
+        ));
+        self.history.insert(&project.id, &history);
+        event.emit();
+    }
+
+    // Drops the timeline of a removed project whose id is used again, so the new project starts
+    // one of its own
+    fn clear_history(&mut self, id: &String) {
+        if let Some(mut history) = self.history.remove(id) {
+            history.clear();
+        }
+    }
+
+    // Stores the project and stamps it with the time of the change
+    fn save_project(&mut self, project: &mut Project) {
+        project.updated_at = U64(env::block_timestamp());
+        self.projects.insert(&project.id, &VersionedProject::from(project.clone()));
+        self.legacy_projects.remove(&project.id);
+    }
+
+    // An id is taken while its project exists and while the refund of its removal is in flight
+    fn is_id_taken(&self, id: &String) -> bool {
+        self.find_project(id).is_some() || self.pending_removals.contains(id)
+    }
+
+    pub(crate) fn get_project(&self, id: &String) -> Result<Project, ProjectError> {
+        self.find_project(id).ok_or(ProjectError::ProjectNotFound)
+    }
+
+    // Reads a project in whichever layout it was stored
+    pub(crate) fn find_project(&self, id: &String) -> Option<Project> {
+        self.projects
+            .get(id)
+            .map(Project::from)
+            .or_else(|| self.legacy_projects.get(id).map(Project::from))
+    }
+
+    // Checks that the predecessor may act on the project in the given role
+    pub(crate) fn authorize(&self, project: &Project, role: Role) -> Result<(), ProjectError> {
+        let caller = env::predecessor_account_id();
+        let is_owner = project.project_owner == caller;
+        let is_worker = project.worker.as_ref() == Some(&caller);
+        match role {
+            Role::ProjectOwner if !is_owner => Err(ProjectError::NotOwner),
+            Role::Worker if !is_worker => {
+                Err(ProjectError::NotWorker)
+            }
+            Role::OwnerOrWorker if !is_owner && !is_worker => {
+                Err(ProjectError::NotOwnerOrWorker)
+            }
+            Role::Arbiter
+                if project.dispute.as_ref().map(|dispute| &dispute.arbiter) != Some(&caller) =>
+            {
+                Err(ProjectError::NotArbiter)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Contract-wide settings can only be changed by the owner of the contract
+    pub(crate) fn authorize_admin(&self) -> Result<(), ProjectError> {
+        if env::predecessor_account_id() != self.owner_id {
+            Err(ProjectError::NotAdmin)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn ensure_not_paused(&self) -> Result<(), ProjectError> {
+        if self.paused {
+            Err(ProjectError::ContractPaused)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Assigns the worker of a bid in AwaitingTopUp once the reward covers it, declines the other
+    // bids and refunds whatever the reward exceeds the bid by.
+    fn complete_awaiting_bid(&mut self, mut project: Project) -> Option<Promise> {
+        let mut bids = self.bids.get(&project.id).unwrap_or_default();
+        let bid = bids
+            .iter()
+            .find(|bid| bid.status == BidStatus::AwaitingTopUp)?;
+        if bid.amount > project.reward {
+            return None;
+        }
+        let worker_id = bid.worker_id.clone();
+        let surplus = project.reward - bid.amount;
+        let previous_status = project.status;
+        project.transition(Status::NotStarted).ok()?;
+        for bid in bids.iter_mut() {
+            bid.status = match bid.status {
+                BidStatus::AwaitingTopUp => BidStatus::Accepted,
+                BidStatus::Pending => BidStatus::Declined,
+                _ => continue,
+            };
+        }
+        self.bids.insert(&project.id, &bids);
+        project.reward -= surplus;
+        project.worker = Some(worker_id.clone());
+        self.save_project(&mut project);
+        self.add_user_project(&worker_id, &project.id);
+        self.record(
+            &project,
+            Some(previous_status),
+            ProjectEvent::WorkerAssigned {
+                project_id: project.id.clone(),
+                worker_id,
+                status: project.status,
+            },
+        );
+        if surplus == 0 {
+            return None;
+        }
+        // The reward is now the bid, so a bounced surplus must not go back into it
+        self.record_refund(&project, surplus);
+        Some(self.refund_owner(&project, surplus))
+    }
+
+    fn add_user_project(&mut self, worker_id: &AccountId, id: &String) {
+        let mut user_projects = self
+            .user_projects
+            .get(worker_id)
+            .unwrap_or_else(|| UnorderedSet::new(worker_prefix(worker_id)));
+        user_projects.insert(id);
+        self.user_projects.insert(worker_id, &user_projects);
+    }
+
+    fn add_owner_project(&mut self, owner_id: &AccountId, id: &String) {
+        let mut owner_projects = self
+            .owner_projects
+            .get(owner_id)
+            .unwrap_or_else(|| UnorderedSet::new(owner_prefix(owner_id)));
+        owner_projects.insert(id);
+        self.owner_projects.insert(owner_id, &owner_projects);
+    }
+
+    fn remove_owner_project(&mut self, owner_id: &AccountId, id: &String) {
+        if let Some(mut owner_projects) = self.owner_projects.get(owner_id) {
+            owner_projects.remove(id);
+            self.owner_projects.insert(owner_id, &owner_projects);
+        }
+    }
+
+    fn remove_user_project(&mut self, worker_id: &AccountId, id: &String) {
+        if let Some(mut user_projects) = self.user_projects.get(worker_id) {
+            user_projects.remove(id);
+            self.user_projects.insert(worker_id, &user_projects);
+        }
+    }
+
+    // Pays the worker and records the outcome on the project through resolve_payout
+    fn pay_worker(&self, project: &Project, receiver_id: AccountId, amount: u128) -> Promise {
+        self.transfer_reward(project, receiver_id.clone(), amount)
+            .then(ext_self::resolve_payout(
+                project.id.clone(),
+                receiver_id,
+                amount,
+                &env::current_account_id(),
+                0,
+                GAS_FOR_RESOLVE_TRANSFER,
+            ))
+    }
+
+    // Refunds the company and records a bounced refund through resolve_owner_refund
+    fn refund_owner(&self, project: &Project, amount: u128) -> Promise {
+        self.transfer_reward(project, project.project_owner.clone(), amount)
+            .then(ext_self::resolve_owner_refund(
+                project.id.clone(),
+                amount,
+                &env::current_account_id(),
+                0,
+                GAS_FOR_RESOLVE_TRANSFER,
+            ))
+    }
+
+    fn resend_payout(
+        &mut self,
+        mut project: Project,
+        receiver_id: AccountId,
+    ) -> Result<Promise, ProjectError> {
+        if project.unpaid_amount == 0 {
+            return Err(ProjectError::NoFailedPayout);
+        }
+        let amount = project.unpaid_amount;
+        project.unpaid_amount = 0;
+        let previous_status = project.status;
+        if project.status == Status::PayoutFailed {
+            // A settled dispute ends the job even when milestones were left unapproved
+            project.transition(
+                if project.dispute.is_some()
+                    || project
+                        .milestones
+                        .iter()
+                        .all(|milestone| milestone.status == MilestoneStatus::Approved)
+                {
+                    Status::Complete
+                } else {
+                    Status::InProgress
+                },
+            )?;
+        }
+        self.save_project(&mut project);
+        self.record_payout(&project, amount);
+        self.record(
+            &project,
+            Some(previous_status),
+            ProjectEvent::PayoutRetried {
+                project_id: project.id.clone(),
+                receiver_id: receiver_id.clone(),
+                amount: U128(amount),
+            },
+        );
+        Ok(self.pay_worker(&project, receiver_id, amount))
+    }
+
+    fn owner_escrow(&self, owner_id: &AccountId) -> OwnerEscrow {
+        self.escrow_ledger
+            .get(owner_id)
+            .unwrap_or_else(|| OwnerEscrow::new(owner_id.clone()))
+    }
+
+    pub(crate) fn record_deposit(&mut self, project: &Project, amount: u128) {
+        if project.token_id.is_some() {
+            return;
+        }
+        let mut escrow = self.owner_escrow(&project.project_owner);
+        escrow.escrowed.0 += amount;
+        self.escrow_ledger.insert(&project.project_owner, &escrow);
+        self.total_escrowed += amount;
+    }
+
+    pub(crate) fn record_refund(&mut self, project: &Project, amount: u128) {
+        if project.token_id.is_some() {
+            return;
+        }
+        let mut escrow = self.owner_escrow(&project.project_owner);
+        escrow.escrowed.0 -= amount;
+        escrow.refunded.0 += amount;
+        self.escrow_ledger.insert(&project.project_owner, &escrow);
+        self.total_escrowed -= amount;
+    }
+
+    // Used by the refund callbacks when the transfer back to the owner failed
+    pub(crate) fn revert_refund(&mut self, project: &Project, amount: u128) {
+        if project.token_id.is_some() {
+            return;
+        }
+        let mut escrow = self.owner_escrow(&project.project_owner);
+        escrow.escrowed.0 += amount;
+        escrow.refunded.0 -= amount;
+        self.escrow_ledger.insert(&project.project_owner, &escrow);
+        self.total_escrowed += amount;
+    }
+
+    pub(crate) fn record_payout(&mut self, project: &Project, amount: u128) {
+        if project.token_id.is_some() {
+            return;
+        }
+        let mut escrow = self.owner_escrow(&project.project_owner);
+        escrow.escrowed.0 -= amount;
+        escrow.paid_out.0 += amount;
+        self.escrow_ledger.insert(&project.project_owner, &escrow);
+        self.total_escrowed -= amount;
+    }
+
+    // Used by resolve_payout when the transfer to the worker failed
+    pub(crate) fn revert_payout(&mut self, project: &Project, amount: u128) {
+        if project.token_id.is_some() {
+            return;
+        }
+        let mut escrow = self.owner_escrow(&project.project_owner);
+        escrow.escrowed.0 += amount;
+        escrow.paid_out.0 -= amount;
+        self.escrow_ledger.insert(&project.project_owner, &escrow);
+        self.total_escrowed += amount;
+    }
+}
+
+// Reads the ids of one page of `ids` along with the from_index of the page after it
+fn page_ids(
+    ids: &UnorderedSet<String>,
+    from_index: Option<U64>,
+    limit: Option<U64>,
+) -> (Vec<String>, Option<U64>) {
+    let ids = ids.as_vector();
+    let (from_index, end, next_index) = page_range(ids.len(), from_index, limit);
+    let page = (from_index..end).filter_map(|index| ids.get(index)).collect();
+    (page, next_index)
+}
+
+// The bounds of one page of a list of `len` items, and the from_index of the page after it
+fn page_range(len: u64, from_index: Option<U64>, limit: Option<U64>) -> (u64, u64, Option<U64>) {
+    let from_index = from_index.map_or(0, |from_index| from_index.0).min(len);
+    let limit = limit
+        .map_or(DEFAULT_PAGE_LIMIT, |limit| limit.0)
+        .clamp(1, MAX_PAGE_LIMIT);
+    let end = from_index.saturating_add(limit).min(len);
+    let next_index = if end < len { Some(U64(end)) } else { None };
+    (from_index, end, next_index)
+}
+
+// Every worker's map gets a prefix of its own, made from a hash of the account id
+fn worker_prefix(worker_id: &AccountId) -> Vec<u8> {
+    let mut prefix = b"n".to_vec();
+    prefix.extend(env::sha256(worker_id.as_bytes()));
+    prefix
+}
+
+// Same as worker_prefix, for the history of a project
+fn history_prefix(id: &str) -> Vec<u8> {
+    let mut prefix = b"t".to_vec();
+    prefix.extend(env::sha256(id.as_bytes()));
+    prefix
+}
+
+// Same as worker_prefix, for the map of a company's projects
+fn owner_prefix(owner_id: &AccountId) -> Vec<u8> {
+    let mut prefix = b"c".to_vec();
+    prefix.extend(env::sha256(owner_id.as_bytes()));
+    prefix
+}
+
+// Payable methods fail the whole call on an error so the attached deposit goes back to the caller.
+// near-sdk keeps the state and the deposit of a call that returns Err.
+fn fail_on_error<T>(result: Result<T, ProjectError>) -> Result<T, ProjectError> {
+    result.map_err(|error| error.panic())
+}
+
+fn parse_amount(amount: &str) -> Result<u128, ProjectError> {
+    match amount.parse() {
+        Ok(amount) => Ok(amount),
+        Err(_) => Err(ProjectError::InvalidAmount {
+            amount: amount.to_string(),
+        }),
+    }
 }
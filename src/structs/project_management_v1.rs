@@ -0,0 +1,14 @@
+use crate::structs::project_v1::ProjectV1;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, UnorderedSet};
+use near_sdk::AccountId;
+
+// The contract state of the first deployed contract. Only read by `migrate`. Every worker's map
+// had the b"p" prefix of `projects`, so it held the same entries.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ProjectManagementV1 {
+    pub(crate) project_ids: UnorderedSet<String>,
+    pub(crate) projects: LookupMap<String, ProjectV1>,
+    pub(crate) user_ids: UnorderedSet<String>,
+    pub(crate) user_projects: LookupMap<AccountId, LookupMap<String, ProjectV1>>,
+}
@@ -0,0 +1,21 @@
+use crate::structs::project::Project;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProjectPage {
+    pub(crate) projects: Vec<Project>,
+    // The from_index of the next page, or None once the last page has been read
+    pub(crate) next_index: Option<U64>,
+}
+
+impl ProjectPage {
+    pub fn new(projects: Vec<Project>, next_index: Option<U64>) -> ProjectPage {
+        ProjectPage {
+            projects,
+            next_index,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::structs::project::Project;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+pub enum SortField {
+    CreatedAt,
+    UpdatedAt,
+    AssignedAt,
+    StartedAt,
+    SubmittedAt,
+    CompletedAt,
+}
+
+// Orders the projects of a listing by one of their timestamps. Projects that have not reached
+// that point yet go last either way.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProjectSort {
+    pub(crate) field: SortField,
+    pub(crate) descending: bool,
+}
+
+impl ProjectSort {
+    fn timestamp(&self, project: &Project) -> Option<u64> {
+        match self.field {
+            SortField::CreatedAt => Some(project.created_at.0),
+            SortField::UpdatedAt => Some(project.updated_at.0),
+            SortField::AssignedAt => project.assigned_at.map(|timestamp| timestamp.0),
+            SortField::StartedAt => project.started_at.map(|timestamp| timestamp.0),
+            SortField::SubmittedAt => project.submitted_at.map(|timestamp| timestamp.0),
+            SortField::CompletedAt => project.completed_at.map(|timestamp| timestamp.0),
+        }
+    }
+
+    pub(crate) fn sort(&self, projects: &mut [Project]) {
+        projects.sort_by(|a, b| match (self.timestamp(a), self.timestamp(b)) {
+            (Some(a), Some(b)) if self.descending => b.cmp(&a),
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+    }
+}
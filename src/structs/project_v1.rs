@@ -0,0 +1,64 @@
+use crate::structs::project::{Project, Status};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::AccountId;
+
+// The statuses of the first contract, in their stored order. Never change this enum.
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub enum StatusV1 {
+    Complete,
+    PendingFinalApproval,
+    InProgress,
+    NotStarted,
+    PendingWorkerApproval,
+    Created,
+}
+
+// A project as the first contract stored it, bare under b"p". Never change this struct. Read
+// ones are turned into the current Project.
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct ProjectV1 {
+    pub(crate) id: String,
+    pub(crate) github_issue_link: String,
+    pub(crate) description: String,
+    pub(crate) reward: u128,
+    pub(crate) status: StatusV1,
+    pub(crate) worker: Option<AccountId>,
+    pub(crate) project_owner: AccountId,
+}
+
+impl From<StatusV1> for Status {
+    fn from(status: StatusV1) -> Self {
+        match status {
+            StatusV1::Complete => Status::Complete,
+            StatusV1::PendingFinalApproval => Status::PendingFinalApproval,
+            StatusV1::InProgress => Status::InProgress,
+            StatusV1::NotStarted => Status::NotStarted,
+            StatusV1::PendingWorkerApproval => Status::PendingWorkerApproval,
+            StatusV1::Created => Status::Created,
+        }
+    }
+}
+
+impl From<ProjectV1> for Project {
+    fn from(old: ProjectV1) -> Self {
+        let mut project = Project::new(
+            old.id,
+            old.github_issue_link,
+            old.description,
+            old.reward,
+            old.project_owner,
+            None,
+        );
+        project.status = Status::from(old.status);
+        project.worker = old.worker;
+        // The first contract paid the whole reward when it completed a job
+        if project.status == Status::Complete {
+            project.paid_amount = project.reward;
+        }
+        // When the project was created is not known
+        project.created_at = U64(0);
+        project.updated_at = U64(0);
+        project
+    }
+}
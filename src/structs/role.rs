@@ -0,0 +1,11 @@
+// Who may call a mutating method on a project. Methods open to anyone do not check a role.
+#[derive(PartialEq, Debug)]
+pub enum Role {
+    // The company that created the project
+    ProjectOwner,
+    // The worker currently assigned to the project
+    Worker,
+    OwnerOrWorker,
+    // The arbiter of the open dispute on the project
+    Arbiter,
+}
@@ -0,0 +1,21 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Submission {
+    pub(crate) pull_request_url: String,
+    pub(crate) notes: String,
+    // The company's reply when it asked for a revision of this submission
+    pub(crate) feedback: Option<String>,
+}
+
+impl Submission {
+    pub fn new(pull_request_url: String, notes: String) -> Submission {
+        Submission {
+            pull_request_url,
+            notes,
+            feedback: None,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::structs::project::Status;
+use crate::structs::project::Status::*;
+
+// Every move a project can make between statuses. All status changes go through
+// Project::transition, which refuses anything not listed here.
+pub(crate) const TRANSITIONS: &[(Status, Status)] = &[
+    // A worker puts themselves forward, or the company accepts an application or bid
+    (Created, PendingWorkerApproval),
+    (Created, NotStarted),
+    // The company approves or declines the worker
+    (PendingWorkerApproval, NotStarted),
+    (PendingWorkerApproval, Created),
+    // Work starts, is handed in, or the worker leaves or is reclaimed
+    (NotStarted, InProgress),
+    (NotStarted, PendingFinalApproval),
+    (NotStarted, Created),
+    (NotStarted, Complete),
+    (InProgress, PendingFinalApproval),
+    (InProgress, Created),
+    (InProgress, Complete),
+    (InProgress, Disputed),
+    (InProgress, PayoutFailed),
+    // The company reviews the work
+    (PendingFinalApproval, InProgress),
+    (PendingFinalApproval, Complete),
+    (PendingFinalApproval, Disputed),
+    // The arbiter settles the dispute
+    (Disputed, Complete),
+    // Payouts that bounce and are sent again
+    (Complete, PayoutFailed),
+    (PayoutFailed, Complete),
+    (PayoutFailed, InProgress),
+];
+
+pub(crate) fn allowed_transitions(from: Status) -> Vec<Status> {
+    TRANSITIONS
+        .iter()
+        .filter(|(transition_from, _)| *transition_from == from)
+        .map(|(_, to)| *to)
+        .collect()
+}
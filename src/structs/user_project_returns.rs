@@ -1,5 +1,6 @@
 use crate::structs::project::Project;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
 use near_sdk::serde::{Deserialize, Serialize};
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -10,6 +11,10 @@ pub struct UserProjectsReturn {
     pub(crate) pending_work_approval: Vec<Project>,
     pub(crate) not_started: Vec<Project>,
     pub(crate) pending_final_approval: Vec<Project>,
+    pub(crate) payout_failed: Vec<Project>,
+    pub(crate) disputed: Vec<Project>,
+    // The from_index of the next page, or None once the last page has been read
+    pub(crate) next_index: Option<U64>,
 }
 
 impl UserProjectsReturn {
@@ -20,6 +25,15 @@ impl UserProjectsReturn {
             pending_work_approval: Vec::new(),
             not_started: Vec::new(),
             pending_final_approval: Vec::new(),
+            payout_failed: Vec::new(),
+            disputed: Vec::new(),
+            next_index: None,
         }
     }
 }
+
+impl Default for UserProjectsReturn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,25 @@
+use crate::structs::project::Project;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+
+// How a project is kept in `projects`. Projects written by the first contract stay bare under the
+// old prefix in `legacy_projects`, so the variants start at V2. Adding a field to Project adds a
+// variant here: reading an entry converts it to the current Project, and saving it writes the
+// newest variant.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedProject {
+    V2(Project),
+}
+
+impl From<VersionedProject> for Project {
+    fn from(project: VersionedProject) -> Self {
+        match project {
+            VersionedProject::V2(project) => project,
+        }
+    }
+}
+
+impl From<Project> for VersionedProject {
+    fn from(project: Project) -> Self {
+        VersionedProject::V2(project)
+    }
+}